@@ -0,0 +1 @@
+pub mod number_theory;
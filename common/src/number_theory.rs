@@ -0,0 +1,281 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// Errors returned by the number theory helpers instead of overflowing or dividing by zero.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum NumberTheoryError {
+    /// The result does not fit into the integer type.
+    Overflow,
+    /// A modulus of zero was given.
+    ZeroModulus,
+    /// The value has no inverse because it is not coprime with the modulus.
+    NotInvertible,
+    /// The congruences contradict each other.
+    NoSolution,
+}
+
+impl Display for NumberTheoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberTheoryError::Overflow => write!(f, "result does not fit into the integer type"),
+            NumberTheoryError::ZeroModulus => write!(f, "modulus must not be zero"),
+            NumberTheoryError::NotInvertible => write!(f, "value is not coprime with the modulus"),
+            NumberTheoryError::NoSolution => write!(f, "congruences have no common solution"),
+        }
+    }
+}
+
+impl Error for NumberTheoryError {}
+
+/// Unsigned integer types the number theory helpers work with.
+pub trait Unsigned:
+    Copy
+    + Eq
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TWO: Self = 2;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u32, u64, u128, usize);
+
+/// Returns the greatest common divisor of `a` and `b`. `gcd(0, 0)` is 0.
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the least common multiple of `a` and `b`, or an error if it does not fit into `T`.
+/// The least common multiple of 0 and any number is 0.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Result<T, NumberTheoryError> {
+    if a == T::ZERO || b == T::ZERO {
+        return Ok(T::ZERO);
+    }
+
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .ok_or(NumberTheoryError::Overflow)
+}
+
+/// Returns the least common multiple of all values, which is 1 for no values.
+pub fn lcm_of<T: Unsigned>(values: impl IntoIterator<Item = T>) -> Result<T, NumberTheoryError> {
+    values.into_iter().try_fold(T::ONE, lcm)
+}
+
+/// Runs the extended Euclidean algorithm and returns `(gcd, x)` such that
+/// `a * x = gcd (mod b)`, with `x` reduced into `[0, b / gcd)`.
+///
+/// The Bézout coefficients of the textbook algorithm alternate in sign, so only their magnitudes
+/// are tracked, and their magnitudes never exceed `b / gcd`. That keeps the whole computation
+/// within `T`. When `b` is 0 the result is `(a, 1)`.
+pub fn extended_gcd<T: Unsigned>(a: T, b: T) -> (T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let mut old_s_negative = false;
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s + quotient * s);
+        old_s_negative = !old_s_negative;
+    }
+
+    if b == T::ZERO {
+        return (old_r, T::ONE);
+    }
+
+    let modulus = b / old_r;
+    let x = old_s % modulus;
+    if old_s_negative && x != T::ZERO {
+        (old_r, modulus - x)
+    } else {
+        (old_r, x)
+    }
+}
+
+/// Returns the inverse of `a` modulo `modulus`, i.e. the `x` in `[0, modulus)` with
+/// `a * x = 1 (mod modulus)`.
+pub fn mod_inverse<T: Unsigned>(a: T, modulus: T) -> Result<T, NumberTheoryError> {
+    if modulus == T::ZERO {
+        return Err(NumberTheoryError::ZeroModulus);
+    }
+
+    let (gcd, x) = extended_gcd(a % modulus, modulus);
+    if modulus == T::ONE {
+        Ok(T::ZERO)
+    } else if gcd != T::ONE {
+        Err(NumberTheoryError::NotInvertible)
+    } else {
+        Ok(x)
+    }
+}
+
+/// Solves a system of congruences `x = remainder (mod modulus)` with the Chinese Remainder Theorem.
+///
+/// The moduli don't have to be coprime. Returns `(x, lcm)` with `x` the smallest non-negative
+/// solution and `lcm` the least common multiple of all moduli, so every solution is `x + k * lcm`.
+/// An empty system returns `(0, 1)`.
+pub fn crt<T: Unsigned>(congruences: &[(T, T)]) -> Result<(T, T), NumberTheoryError> {
+    congruences
+        .iter()
+        .try_fold((T::ZERO, T::ONE), |acc, &congruence| {
+            merge_congruences(acc, congruence)
+        })
+}
+
+// Merges x = a1 (mod m1) and x = a2 (mod m2) into a single congruence modulo lcm(m1, m2).
+// With g = gcd(m1, m2) the system is solvable only if a1 = a2 (mod g), and then
+// x = a1 + m1 * k with k = (a2 - a1) / g * inverse(m1 / g) (mod m2 / g).
+fn merge_congruences<T: Unsigned>(
+    (a1, m1): (T, T),
+    (a2, m2): (T, T),
+) -> Result<(T, T), NumberTheoryError> {
+    if m1 == T::ZERO || m2 == T::ZERO {
+        return Err(NumberTheoryError::ZeroModulus);
+    }
+
+    let (a1, a2) = (a1 % m1, a2 % m2);
+    let gcd = gcd(m1, m2);
+    if a1 % gcd != a2 % gcd {
+        return Err(NumberTheoryError::NoSolution);
+    }
+
+    let lcm = (m1 / gcd)
+        .checked_mul(m2)
+        .ok_or(NumberTheoryError::Overflow)?;
+    let m2_reduced = m2 / gcd;
+    let difference = if a2 >= a1 {
+        (a2 - a1) / gcd % m2_reduced
+    } else {
+        (m2_reduced - (a1 - a2) / gcd % m2_reduced) % m2_reduced
+    };
+    let k = mul_mod(difference, mod_inverse(m1 / gcd, m2_reduced)?, m2_reduced);
+
+    // a1 < m1 and k < m2 / gcd, so a1 + m1 * k < lcm and nothing below can overflow
+    Ok((a1 + m1 * k, lcm))
+}
+
+// Multiplies modulo `modulus` by doubling, so that no intermediate value exceeds the modulus.
+fn mul_mod<T: Unsigned>(mut a: T, mut b: T, modulus: T) -> T {
+    let mut result = T::ZERO;
+    a = a % modulus;
+    while b != T::ZERO {
+        if b % T::TWO == T::ONE {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b / T::TWO;
+    }
+    result
+}
+
+// Adds two values that are already reduced modulo `modulus`, without overflowing.
+fn add_mod<T: Unsigned>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, 0, 0)]
+    #[case(0, 7, 0)]
+    #[case(4, 6, 12)]
+    #[case(21, 6, 42)]
+    #[case(u64::MAX, 1, u64::MAX)]
+    fn lcm_test(#[case] a: u64, #[case] b: u64, #[case] expected: u64) {
+        assert_eq!(lcm(a, b), Ok(expected));
+        assert_eq!(gcd(a, b) * expected, a * b);
+    }
+
+    #[test]
+    fn lcm_overflow_test() {
+        assert_eq!(
+            lcm(u64::MAX, u64::MAX - 1),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(
+            lcm_of([1u128 << 100, 5, (1 << 27) + 1]),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(lcm_of([1u128 << 100, 3, 1 << 26]), Ok(3 << 100));
+        assert_eq!(lcm_of(Vec::<u64>::new()), Ok(1));
+    }
+
+    #[rstest]
+    #[case(240, 46, 2, 14)]
+    #[case(3, 7, 1, 5)]
+    #[case(7, 3, 1, 1)]
+    #[case(12, 0, 12, 1)]
+    #[case(0, 12, 12, 0)]
+    fn extended_gcd_test(#[case] a: u64, #[case] b: u64, #[case] gcd: u64, #[case] x: u64) {
+        assert_eq!(extended_gcd(a, b), (gcd, x));
+    }
+
+    #[test]
+    fn mod_inverse_test() {
+        assert_eq!(mod_inverse(3u64, 11), Ok(4));
+        assert_eq!(mod_inverse(10u64, 17), Ok(12));
+        assert_eq!(mod_inverse(6u64, 9), Err(NumberTheoryError::NotInvertible));
+        assert_eq!(mod_inverse(6u64, 0), Err(NumberTheoryError::ZeroModulus));
+
+        let modulus = u128::MAX;
+        let inverse = mod_inverse(2u128, modulus).expect("2 should be invertible");
+        assert_eq!(mul_mod(2, inverse, modulus), 1);
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(crt(&[(2u64, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt(&[(3u64, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(
+            crt(&[(3u64, 4), (4, 6)]),
+            Err(NumberTheoryError::NoSolution)
+        );
+        assert_eq!(crt::<u64>(&[]), Ok((0, 1)));
+        assert_eq!(
+            crt(&[(1u64, u64::MAX), (0, 2)]),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(
+            crt(&[(1u128, u64::MAX as u128), (0, 2)]),
+            Ok((u64::MAX as u128 + 1, 2 * u64::MAX as u128))
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use crate::bag::Bag;
+use crate::parser::{Color, Game};
+
+/// Returns the largest number of cubes of every colour drawn in any round of any game, which is
+/// also the smallest bag that makes every game possible.
+pub fn color_maximums(games: &[Game]) -> Bag {
+    games
+        .iter()
+        .flat_map(|game| game.rounds.iter().flatten())
+        .fold(Bag::new(), |bag, cube| {
+            let count = bag.get(&cube.color).max(cube.count);
+            bag.with(cube.color.clone(), count)
+        })
+}
+
+/// For every colour, lists the ids of the games that are not possible with `bag`, but would be
+/// if one more cube of that colour was added. Colours that don't make any new game possible are
+/// left out.
+pub fn possible_after_one_more_cube(games: &[Game], bag: &Bag) -> BTreeMap<Color, Vec<u32>> {
+    let impossible: Vec<&Game> = games.iter().filter(|game| !bag.is_possible(game)).collect();
+
+    color_maximums(games)
+        .iter()
+        .filter_map(|(color, _)| {
+            let enlarged = bag.clone().with(color.clone(), bag.get(color) + 1);
+            let ids: Vec<u32> = impossible
+                .iter()
+                .filter(|game| enlarged.is_possible(game))
+                .map(|game| game.id)
+                .collect();
+            (!ids.is_empty()).then(|| (color.clone(), ids))
+        })
+        .collect()
+}
+
+/// Finds the bag with the fewest cubes that makes at least `k` of the games possible, or `None`
+/// if there are fewer than `k` games.
+///
+/// A bag makes a game possible if it holds at least the game's [`Bag::minimum_bag`], so the
+/// count of every colour in the best bag is the count of some game's minimum bag. All such counts
+/// are tried for every colour but the last, and the last one takes the `k`-th smallest count of
+/// the games that still fit. This is exponential in the number of colours, but the puzzle only
+/// has three.
+pub fn smallest_bag_for(games: &[Game], k: usize) -> Option<Bag> {
+    if k > games.len() {
+        return None;
+    }
+    if k == 0 {
+        return Some(Bag::new());
+    }
+
+    let minimum_bags: Vec<Bag> = games.iter().map(Bag::minimum_bag).collect();
+    let candidates: Vec<&Bag> = minimum_bags.iter().collect();
+    let colors: Vec<Color> = color_maximums(games)
+        .iter()
+        .map(|(color, _)| color.clone())
+        .collect();
+
+    let mut best = None;
+    search(&candidates, &colors, k, Bag::new(), &mut best);
+    best
+}
+
+fn search(candidates: &[&Bag], colors: &[Color], k: usize, bag: Bag, best: &mut Option<Bag>) {
+    if best
+        .as_ref()
+        .is_some_and(|best| best.cube_count() <= bag.cube_count())
+    {
+        return;
+    }
+
+    let Some((color, rest)) = colors.split_first() else {
+        *best = Some(bag);
+        return;
+    };
+
+    let mut counts: Vec<u32> = candidates.iter().map(|other| other.get(color)).collect();
+    counts.sort_unstable();
+
+    if rest.is_empty() {
+        let count = counts[k - 1];
+        search(candidates, rest, k, with_count(bag, color, count), best);
+        return;
+    }
+
+    counts.dedup();
+    for count in counts {
+        let fitting: Vec<&Bag> = candidates
+            .iter()
+            .filter(|other| other.get(color) <= count)
+            .copied()
+            .collect();
+        if fitting.len() >= k {
+            search(
+                &fitting,
+                rest,
+                k,
+                with_count(bag.clone(), color, count),
+                best,
+            );
+        }
+    }
+}
+
+/// Colours without cubes are left out, so they don't show up as `0 red` in the bag.
+fn with_count(bag: Bag, color: &Color, count: u32) -> Bag {
+    if count == 0 {
+        bag
+    } else {
+        bag.with(color.clone(), count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_games;
+
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn games(input: &str) -> Vec<Game> {
+        let (_, games) = parse_games(input).expect("should parse games");
+        games
+    }
+
+    #[test]
+    fn d02_color_maximums() {
+        let games = games(INPUT);
+        let maximums = color_maximums(&games);
+        assert_eq!(
+            maximums,
+            Bag::puzzle().with(Color::Red, 20).with(Color::Blue, 15)
+        );
+        assert!(games.iter().all(|game| maximums.is_possible(game)));
+
+        let games = self::games("Game 1: 2 purple, 1 red; 3 purple");
+        assert_eq!(
+            color_maximums(&games),
+            Bag::new()
+                .with(Color::Red, 1)
+                .with(Color::Other("purple".to_string()), 3)
+        );
+    }
+
+    #[test]
+    fn d02_possible_after_one_more_cube() {
+        let games = games(INPUT);
+
+        let bag: Bag = "14 red, 13 green, 14 blue"
+            .parse()
+            .expect("should parse bag");
+        let enlarged = possible_after_one_more_cube(&games, &bag);
+        assert_eq!(enlarged, BTreeMap::from([(Color::Blue, vec![4])]));
+
+        // Game 5 needs one more red and one more green cube, so no single cube is enough
+        let bag: Bag = "5 red, 2 green, 6 blue".parse().expect("should parse bag");
+        let enlarged = possible_after_one_more_cube(&games, &bag);
+        assert_eq!(enlarged, BTreeMap::from([(Color::Green, vec![2])]));
+
+        assert!(possible_after_one_more_cube(&games, &color_maximums(&games)).is_empty());
+    }
+
+    #[test]
+    fn d02_smallest_bag_for() {
+        let games = games(INPUT);
+
+        // No game has to be possible, so the empty bag is enough
+        assert_eq!(smallest_bag_for(&games, 0), Some(Bag::new()));
+        // Game 2 alone needs 1 red, 3 green and 4 blue cubes
+        assert_eq!(
+            smallest_bag_for(&games, 1),
+            Some("1 red, 3 green, 4 blue".parse().expect("should parse bag"))
+        );
+        // Games 1, 2 and 5 fit into 6 red, 3 green and 6 blue cubes
+        assert_eq!(
+            smallest_bag_for(&games, 3),
+            Some("6 red, 3 green, 6 blue".parse().expect("should parse bag"))
+        );
+        assert_eq!(smallest_bag_for(&games, 5), Some(color_maximums(&games)));
+        assert_eq!(smallest_bag_for(&games, 6), None);
+    }
+
+    #[test]
+    fn d02_smallest_bag_for_brute_force() {
+        let games = games(INPUT);
+        let maximums = color_maximums(&games);
+
+        for k in 1..=games.len() {
+            let mut fewest = u64::MAX;
+            for red in 0..=maximums.get(&Color::Red) {
+                for green in 0..=maximums.get(&Color::Green) {
+                    for blue in 0..=maximums.get(&Color::Blue) {
+                        let bag = Bag::new()
+                            .with(Color::Red, red)
+                            .with(Color::Green, green)
+                            .with(Color::Blue, blue);
+                        if games.iter().filter(|game| bag.is_possible(game)).count() >= k {
+                            fewest = fewest.min(bag.cube_count());
+                        }
+                    }
+                }
+            }
+
+            let bag = smallest_bag_for(&games, k).expect("should find a bag");
+            assert!(games.iter().filter(|game| bag.is_possible(game)).count() >= k);
+            assert_eq!(bag.cube_count(), fewest);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use nom::character::complete::multispace0;
+use nom::combinator::all_consuming;
+use nom::sequence::delimited;
+
+use crate::parser::{round, Color, Game};
+
+/// The cubes in the bag: how many cubes there are of each colour. Colours that were never added
+/// have no cubes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag {
+    counts: BTreeMap<Color, u32>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BagError {
+    Syntax(String),
+    DuplicateColor(Color),
+}
+
+impl fmt::Display for BagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagError::Syntax(error) => write!(f, "unable to parse bag: {error}"),
+            BagError::DuplicateColor(color) => write!(f, "bag lists {color} cubes more than once"),
+        }
+    }
+}
+
+impl Error for BagError {}
+
+impl Bag {
+    pub fn new() -> Self {
+        Bag::default()
+    }
+
+    /// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+    pub fn puzzle() -> Self {
+        Bag::new()
+            .with(Color::Red, 12)
+            .with(Color::Green, 13)
+            .with(Color::Blue, 14)
+    }
+
+    /// Returns the bag with `count` cubes of `color`, replacing any previous count.
+    pub fn with(mut self, color: Color, count: u32) -> Self {
+        self.counts.insert(color, count);
+        self
+    }
+
+    /// Returns the number of cubes of `color`, 0 if the bag has none.
+    pub fn get(&self, color: &Color) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    /// Iterates over the colours in the bag and their counts, in colour order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Color, u32)> {
+        self.counts.iter().map(|(color, &count)| (color, count))
+    }
+
+    /// Checks if every round of `game` could have been drawn from this bag.
+    pub fn is_possible(&self, game: &Game) -> bool {
+        game.rounds
+            .iter()
+            .flatten()
+            .all(|cube| cube.count <= self.get(&cube.color))
+    }
+
+    /// Returns the bag with the fewest cubes of every colour that makes `game` possible.
+    pub fn minimum_bag(game: &Game) -> Self {
+        let mut bag = Bag::new();
+        for cube in game.rounds.iter().flatten() {
+            let count = bag.counts.entry(cube.color.clone()).or_insert(0);
+            *count = (*count).max(cube.count);
+        }
+        bag
+    }
+
+    /// Returns the total number of cubes in the bag.
+    pub fn cube_count(&self) -> u64 {
+        self.counts.values().map(|&count| u64::from(count)).sum()
+    }
+
+    /// Multiplies the red, green and blue counts, as the puzzle defines it. A missing colour
+    /// counts as 0 and any other colour is ignored.
+    pub fn power(&self) -> u32 {
+        self.get(&Color::Red) * self.get(&Color::Green) * self.get(&Color::Blue)
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, (color, count)) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{count} {color}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a bag written like a round of the game, e.g. `12 red, 13 green, 14 blue`, so it can be
+/// passed on the command line or kept in a config file.
+///
+/// # Example
+///
+/// ```
+/// use day02::bag::Bag;
+/// use day02::parser::Color;
+///
+/// let bag: Bag = "12 red, 13 green, 2 yellow".parse().expect("should parse bag");
+/// assert_eq!(bag.get(&Color::Green), 13);
+/// assert_eq!(bag.get(&Color::Other("yellow".to_string())), 2);
+/// assert_eq!(bag.get(&Color::Blue), 0);
+/// ```
+impl FromStr for Bag {
+    type Err = BagError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (_, cubes) = all_consuming(delimited(multispace0, round, multispace0))(input)
+            .map_err(|error| BagError::Syntax(error.to_string()))?;
+
+        let mut bag = Bag::new();
+        for cube in cubes {
+            if bag.counts.contains_key(&cube.color) {
+                return Err(BagError::DuplicateColor(cube.color));
+            }
+            bag.counts.insert(cube.color, cube.count);
+        }
+        Ok(bag)
+    }
+}
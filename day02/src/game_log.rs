@@ -0,0 +1,92 @@
+use std::env;
+
+use day02::analytics::{color_maximums, possible_after_one_more_cube, smallest_bag_for};
+use day02::bag::Bag;
+use day02::parser::parse_games;
+
+const USAGE: &str =
+    "usage: d02log maximums | enlarge [\"12 red, 13 green, 14 blue\"] | smallest <k> (k >= 1)";
+
+fn main() {
+    let input_file = include_str!("../input_p1.txt");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    println!("{}", run(input_file, &args));
+}
+
+fn run(input: &str, args: &[&str]) -> String {
+    let (_, games) = parse_games(input).expect("should parse games");
+
+    match args {
+        ["maximums"] => color_maximums(&games).to_string(),
+        ["enlarge", rest @ ..] if rest.len() <= 1 => {
+            let bag = match rest.first() {
+                Some(spec) => match spec.parse::<Bag>() {
+                    Ok(bag) => bag,
+                    Err(error) => return error.to_string(),
+                },
+                None => Bag::puzzle(),
+            };
+            possible_after_one_more_cube(&games, &bag)
+                .iter()
+                .map(|(color, ids)| format!("+1 {color}: games {ids:?}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+        ["smallest", k] => match k.parse() {
+            Ok(0) | Err(_) => USAGE.to_string(),
+            Ok(k) => match smallest_bag_for(&games, k) {
+                Some(bag) => format!("{bag} ({} cubes)", bag.cube_count()),
+                None => format!("there are only {} games", games.len()),
+            },
+        },
+        _ => USAGE.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn d02log_maximums() {
+        assert_eq!(run(INPUT, &["maximums"]), "20 red, 13 green, 15 blue");
+    }
+
+    #[test]
+    fn d02log_enlarge() {
+        assert_eq!(
+            run(INPUT, &["enlarge", "14 red, 13 green, 14 blue"]),
+            "+1 blue: games [4]"
+        );
+        assert_eq!(run(INPUT, &["enlarge"]), "");
+        assert!(run(INPUT, &["enlarge", "1 red; 2 blue"]).starts_with("unable to parse bag"));
+    }
+
+    #[test]
+    fn d02log_smallest() {
+        // Game 2 alone needs 1 red, 3 green and 4 blue cubes
+        assert_eq!(
+            run(INPUT, &["smallest", "1"]),
+            "1 red, 3 green, 4 blue (8 cubes)"
+        );
+        // Games 1, 2 and 5 fit into 6 red, 3 green and 6 blue cubes
+        assert_eq!(
+            run(INPUT, &["smallest", "3"]),
+            "6 red, 3 green, 6 blue (15 cubes)"
+        );
+        assert_eq!(
+            run(INPUT, &["smallest", "5"]),
+            "20 red, 13 green, 15 blue (48 cubes)"
+        );
+        assert_eq!(run(INPUT, &["smallest", "6"]), "there are only 5 games");
+        assert_eq!(run(INPUT, &["smallest", "0"]), USAGE);
+        assert_eq!(run(INPUT, &["smallest"]), USAGE);
+    }
+}
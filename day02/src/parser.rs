@@ -1,3 +1,5 @@
+use std::fmt;
+
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::character::complete;
 use nom::character::complete::{alpha1, digit1, line_ending};
@@ -6,16 +8,48 @@ use nom::multi::separated_list1;
 use nom::sequence::{preceded, separated_pair};
 use nom::IResult;
 
-#[derive(Debug)]
-pub struct Cube<'a> {
-    pub color: &'a str,
+/// Colour of a cube. The puzzle only uses red, green and blue, but any other name is kept as
+/// [`Color::Other`] instead of being dropped.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+    Other(String),
+}
+
+impl Color {
+    pub fn new(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "blue" => Color::Blue,
+            other => Color::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Red => write!(f, "red"),
+            Color::Green => write!(f, "green"),
+            Color::Blue => write!(f, "blue"),
+            Color::Other(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cube {
+    pub color: Color,
     pub count: u32,
 }
 
-#[derive(Debug)]
-pub struct Game<'a> {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
     pub id: u32,
-    pub rounds: Vec<Vec<Cube<'a>>>,
+    pub rounds: Vec<Vec<Cube>>,
 }
 
 pub fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
@@ -31,7 +65,7 @@ fn game(input: &str) -> IResult<&str, Game> {
 }
 
 // Example input: 3 blue, 4 red
-fn round(input: &str) -> IResult<&str, Vec<Cube>> {
+pub(crate) fn round(input: &str) -> IResult<&str, Vec<Cube>> {
     let (input, cubes) = separated_list1(tag(", "), cube)(input)?;
     Ok((input, cubes))
 }
@@ -39,5 +73,11 @@ fn round(input: &str) -> IResult<&str, Vec<Cube>> {
 // Example input: 4 red
 fn cube(input: &str) -> IResult<&str, Cube> {
     let (input, (count, color)) = separated_pair(complete::u32, tag(" "), alpha1)(input)?;
-    Ok((input, Cube { color, count }))
+    Ok((
+        input,
+        Cube {
+            color: Color::new(color),
+            count,
+        },
+    ))
 }
@@ -1,45 +1,55 @@
-use std::ops::Not;
+use std::env;
+use std::fs;
 
 use rayon::prelude::*;
 
-use crate::parser::parse_games;
-
-mod parser;
-
-const RED_MAX_COUNT: u32 = 12;
-const GREEN_MAX_COUNT: u32 = 13;
-const BLUE_MAX_COUNT: u32 = 14;
+use day02::bag::Bag;
+use day02::parser::parse_games;
 
 fn main() {
     let input_file = include_str!("../input_p1.txt");
-    let solution = part1(input_file);
+    let bag = bag_from_args();
+    let solution = part1(input_file, &bag);
     println!("{solution}");
 }
 
-fn part1(input: &str) -> u32 {
-    let games = parse_games(input).expect("should parse games");
+/// Reads the bag from `--bag "12 red, 13 green, 14 blue"` or from a file given with
+/// `--bag-file <path>`, falling back to the bag from the puzzle.
+fn bag_from_args() -> Bag {
+    let args: Vec<String> = env::args().collect();
+    let value_of = |flag: &str| {
+        args.iter().position(|arg| arg == flag).map(|index| {
+            args.get(index + 1)
+                .expect("flag should be followed by a value")
+        })
+    };
+
+    if let Some(spec) = value_of("--bag") {
+        spec.parse().expect("should parse bag")
+    } else if let Some(path) = value_of("--bag-file") {
+        fs::read_to_string(path)
+            .expect("should read bag file")
+            .parse()
+            .expect("should parse bag")
+    } else {
+        Bag::puzzle()
+    }
+}
+
+fn part1(input: &str, bag: &Bag) -> u32 {
+    let (_, games) = parse_games(input).expect("should parse games");
     games
-        .1
         .par_iter()
-        .filter_map(|game| {
-            game.rounds
-                .iter()
-                .any(|round| {
-                    round.iter().any(|cube| {
-                        // Game is always valid, unless there is round where number of cubes is grater than max counts
-                        cube.color == "red" && cube.count > RED_MAX_COUNT
-                            || cube.color == "green" && cube.count > GREEN_MAX_COUNT
-                            || cube.color == "blue" && cube.count > BLUE_MAX_COUNT
-                    })
-                })
-                .not()
-                .then_some(game.id)
-        })
+        .filter(|game| bag.is_possible(game))
+        .map(|game| game.id)
         .sum::<u32>()
 }
 
 #[cfg(test)]
 mod tests {
+    use day02::bag::BagError;
+    use day02::parser::Color;
+
     use super::*;
 
     #[test]
@@ -50,6 +60,34 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-        assert_eq!(part1(input), 8);
+        assert_eq!(part1(input, &Bag::puzzle()), 8);
+    }
+
+    #[test]
+    fn d02p1_custom_bag() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 2 yellow, 1 red; 1 blue";
+
+        let bag: Bag = "4 red, 2 green, 6 blue".parse().expect("should parse bag");
+        assert_eq!(part1(input, &bag), 1);
+
+        // Game 3 draws yellow cubes, which are only possible once the bag has some
+        let bag = bag.with(Color::Green, 3).with(Color::new("Yellow"), 2);
+        assert_eq!(part1(input, &bag), 6);
+        assert_eq!(bag.to_string(), "4 red, 3 green, 6 blue, 2 yellow");
+        assert_eq!(Ok(bag.clone()), bag.to_string().parse());
+    }
+
+    #[test]
+    fn d02p1_invalid_bag() {
+        assert_eq!(
+            "1 red, 2 red".parse::<Bag>(),
+            Err(BagError::DuplicateColor(Color::Red))
+        );
+        assert!(matches!(
+            "1 red; 2 blue".parse::<Bag>(),
+            Err(BagError::Syntax(_))
+        ));
     }
 }
@@ -1,8 +1,7 @@
 use rayon::prelude::*;
 
-use crate::parser::parse_games;
-
-mod parser;
+use day02::bag::Bag;
+use day02::parser::parse_games;
 
 fn main() {
     let input_file = include_str!("../input_p2.txt");
@@ -11,43 +10,17 @@ fn main() {
 }
 
 fn part2(input: &str) -> u32 {
-    let games = parse_games(input).expect("should parse games");
+    let (_, games) = parse_games(input).expect("should parse games");
     games
-        .1
         .par_iter()
-        .map(|game| {
-            let mut red_required: u32 = 0;
-            let mut green_required: u32 = 0;
-            let mut blue_required: u32 = 0;
-
-            game.rounds.iter().for_each(|round| {
-                round.iter().for_each(|cube| match cube.color {
-                    "red" => {
-                        if cube.count > red_required {
-                            red_required = cube.count;
-                        }
-                    }
-                    "green" => {
-                        if cube.count > green_required {
-                            green_required = cube.count;
-                        }
-                    }
-                    "blue" => {
-                        if cube.count > blue_required {
-                            blue_required = cube.count;
-                        }
-                    }
-                    _ => {}
-                })
-            });
-
-            red_required * green_required * blue_required
-        })
+        .map(|game| Bag::minimum_bag(game).power())
         .sum::<u32>()
 }
 
 #[cfg(test)]
 mod tests {
+    use day02::parser::Color;
+
     use super::*;
 
     #[test]
@@ -60,4 +33,29 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
         assert_eq!(part2(input), 2286);
     }
+
+    #[test]
+    fn d02p2_minimum_bag() {
+        let input = "Game 7: 3 blue, 4 red; 2 purple, 6 blue; 5 purple";
+        let (_, games) = parse_games(input).expect("should parse games");
+        let bag = Bag::minimum_bag(&games[0]);
+
+        assert_eq!(bag.get(&Color::Blue), 6);
+        assert_eq!(bag.get(&Color::Red), 4);
+        assert_eq!(bag.get(&Color::Other("purple".to_string())), 5);
+        assert_eq!(bag.get(&Color::Green), 0);
+        assert!(bag.is_possible(&games[0]));
+        assert!(!bag.clone().with(Color::Blue, 5).is_possible(&games[0]));
+        assert_eq!(bag.power(), 0);
+        assert_eq!(bag.with(Color::Green, 2).power(), 48);
+    }
+
+    #[test]
+    fn d02p2_missing_color() {
+        assert_eq!(part2("Game 1: 3 blue, 4 red"), 0);
+        assert_eq!(
+            part2("Game 1: 3 blue, 4 red; 1 green\nGame 2: 2 blue, 1 red"),
+            12
+        );
+    }
 }
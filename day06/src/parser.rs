@@ -1,40 +1,158 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::iter::zip;
+use std::ops::RangeInclusive;
 
 use nom::bytes::complete::tag_no_case;
-use nom::character::complete::{digit1, multispace1, space1};
-use nom::combinator::map_res;
+use nom::character::complete::{digit1, multispace0, multispace1, space1};
+use nom::combinator::all_consuming;
 use nom::multi::separated_list1;
 use nom::sequence::{preceded, tuple};
 use nom::IResult;
 
 #[derive(Debug)]
 pub struct Race {
-    pub time: u64,
-    pub distance: u64,
+    pub time: u128,
+    pub distance: u128,
 }
 
-pub fn parse_races_part1(input: &str) -> IResult<&str, Vec<Race>> {
-    let (input, times) = preceded(
-        tuple((tag_no_case("Time:"), space1)),
-        separated_list1(space1, parse_u64),
-    )(input)?;
+impl Race {
+    /// Returns the inclusive range of hold times that beat the record distance,
+    /// or `None` when the record cannot be beaten at all.
+    ///
+    /// Holding the button for `h` milliseconds travels `h * (time - h)` millimeters, so the winning
+    /// hold times are the integer solutions of `h^2 - time * h + distance < 0`. The lower root is
+    /// computed with an exact integer square root of the discriminant and then nudged onto the
+    /// first winning hold time. The interval is symmetric around `time / 2`, so the upper bound
+    /// is `time - lo`. When the discriminant does not fit into `u128`, the lower bound is found by
+    /// bisecting `[0, time / 2]` instead.
+    pub fn winning_interval(&self) -> Option<RangeInclusive<u128>> {
+        let peak = self.time / 2;
+        if !self.beats_record(peak) {
+            return None;
+        }
 
-    let (input, distances) = preceded(
-        tuple((multispace1, tag_no_case("Distance:"), space1)),
-        separated_list1(space1, parse_u64),
-    )(input)?;
+        let lo = match self.discriminant() {
+            Some(discriminant) => {
+                let mut lo = (self.time - discriminant.isqrt()) / 2;
+                while lo > 0 && self.beats_record(lo - 1) {
+                    lo -= 1;
+                }
+                while !self.beats_record(lo) {
+                    lo += 1;
+                }
+                lo
+            }
+            None => self.first_winning_hold_time(peak),
+        };
+
+        Some(lo..=self.time - lo)
+    }
 
-    let races = zip(times, distances)
-        .map(|race| Race {
-            time: race.0,
-            distance: race.1,
+    /// Returns the number of hold times that beat the record distance.
+    pub fn ways_to_win(&self) -> u128 {
+        self.winning_interval()
+            .map_or(0, |interval| interval.end() - interval.start() + 1)
+    }
+
+    fn discriminant(&self) -> Option<u128> {
+        self.time
+            .checked_mul(self.time)?
+            .checked_sub(self.distance.checked_mul(4)?)
+    }
+
+    // Bisects [0, peak] for the first winning hold time. Holding for 0 never wins and holding for
+    // `peak` is known to win, and the travelled distance only grows on that interval.
+    fn first_winning_hold_time(&self, peak: u128) -> u128 {
+        let (mut losing, mut winning) = (0, peak);
+        while winning - losing > 1 {
+            let mid = losing + (winning - losing) / 2;
+            if self.beats_record(mid) {
+                winning = mid;
+            } else {
+                losing = mid;
+            }
+        }
+        winning
+    }
+
+    fn beats_record(&self, hold_time: u128) -> bool {
+        // A product that overflows u128 is necessarily greater than any u128 record
+        hold_time
+            .checked_mul(self.time - hold_time)
+            .is_none_or(|distance| distance > self.distance)
+    }
+}
+
+/// Both readings of the race sheet: the separate races from part 1 and the single race from
+/// part 2, where the spaces between the numbers are just bad kerning. The kerned race keeps its
+/// own error, so a sheet whose concatenated values overflow can still be read as separate races.
+#[derive(Debug)]
+pub struct RaceSheet {
+    pub races: Vec<Race>,
+    pub kerned_race: Result<Race, RaceSheetError>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RaceSheetError {
+    Syntax(String),
+    ColumnCountMismatch { times: usize, distances: usize },
+    Overflow { row: &'static str, digits: String },
+}
+
+impl Display for RaceSheetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RaceSheetError::Syntax(error) => write!(f, "unable to parse race sheet: {error}"),
+            RaceSheetError::ColumnCountMismatch { times, distances } => write!(
+                f,
+                "race sheet has {times} time columns but {distances} distance columns"
+            ),
+            RaceSheetError::Overflow { row, digits } => {
+                write!(f, "{row} value {digits} does not fit into u128")
+            }
+        }
+    }
+}
+
+impl Error for RaceSheetError {}
+
+/// Parses the `Time:`/`Distance:` rows once and returns both interpretations of the sheet.
+///
+/// Fails when the two rows have a different number of columns, or when a single value does not
+/// fit into `u128`. Concatenated values that don't fit only fail [`RaceSheet::kerned_race`].
+pub fn parse_race_sheet(input: &str) -> Result<RaceSheet, RaceSheetError> {
+    let (_, (times, distances)) =
+        parse_rows(input).map_err(|error| RaceSheetError::Syntax(error.to_string()))?;
+
+    if times.len() != distances.len() {
+        return Err(RaceSheetError::ColumnCountMismatch {
+            times: times.len(),
+            distances: distances.len(),
+        });
+    }
+
+    let races = zip(&times, &distances)
+        .map(|(time, distance)| {
+            Ok(Race {
+                time: digits_to_u128("Time", &[time])?,
+                distance: digits_to_u128("Distance", &[distance])?,
+            })
+        })
+        .collect::<Result<Vec<Race>, RaceSheetError>>()?;
+
+    let kerned_race = digits_to_u128("Time", &times).and_then(|time| {
+        Ok(Race {
+            time,
+            distance: digits_to_u128("Distance", &distances)?,
         })
-        .collect();
+    });
 
-    Ok((input, races))
+    Ok(RaceSheet { races, kerned_race })
 }
 
-pub fn parse_race_part2(input: &str) -> IResult<&str, Race> {
+fn parse_rows(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     let (input, times) = preceded(
         tuple((tag_no_case("Time:"), space1)),
         separated_list1(space1, digit1),
@@ -45,19 +163,21 @@ pub fn parse_race_part2(input: &str) -> IResult<&str, Race> {
         separated_list1(space1, digit1),
     )(input)?;
 
-    let concat_time: String = times.into_iter().collect();
-    let concat_distance: String = distances.into_iter().collect();
+    let (input, _) = all_consuming(multispace0)(input)?;
 
-    let race = Race {
-        time: concat_time.parse().expect("should parse concatenated time"),
-        distance: concat_distance
-            .parse()
-            .expect("should parse concatenated distance"),
-    };
-
-    Ok((input, race))
+    Ok((input, (times, distances)))
 }
 
-fn parse_u64(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse::<u64>)(input)
+// Reads the digits of all columns as one number, without going through an intermediate String
+fn digits_to_u128(row: &'static str, columns: &[&str]) -> Result<u128, RaceSheetError> {
+    columns
+        .iter()
+        .flat_map(|column| column.bytes())
+        .try_fold(0u128, |acc, digit| {
+            acc.checked_mul(10)?.checked_add(u128::from(digit - b'0'))
+        })
+        .ok_or_else(|| RaceSheetError::Overflow {
+            row,
+            digits: columns.concat(),
+        })
 }
@@ -1,8 +1,6 @@
 use std::time::Instant;
 
-use crate::parser::parse_races_part1;
-
-mod parser;
+use day06::parser::{parse_race_sheet, Race};
 
 fn main() {
     let start_time = Instant::now();
@@ -12,24 +10,16 @@ fn main() {
     println!("[{execution_time:?} seconds] {solution}");
 }
 
-fn part1(input: &str) -> u64 {
-    let (_, races) = parse_races_part1(input).expect("should parse input");
-
-    races
-        .iter()
-        .map(|race| {
-            (1..race.time)
-                .filter(|speed| {
-                    let distance = speed * (race.time - speed);
-                    distance > race.distance
-                })
-                .count() as u64
-        })
-        .product()
+fn part1(input: &str) -> u128 {
+    let races = parse_race_sheet(input).expect("should parse input").races;
+
+    races.iter().map(Race::ways_to_win).product()
 }
 
 #[cfg(test)]
 mod tests {
+    use day06::parser::RaceSheetError;
+
     use super::*;
 
     #[test]
@@ -39,4 +29,63 @@ Distance:  9  40  200";
 
         assert_eq!(part1(input), 288);
     }
+
+    #[test]
+    fn d06p1_mismatched_columns() {
+        let input = "Time:      7  15   30
+Distance:  9  40";
+
+        assert_eq!(
+            parse_race_sheet(input).unwrap_err(),
+            RaceSheetError::ColumnCountMismatch {
+                times: 3,
+                distances: 2
+            }
+        );
+    }
+
+    #[test]
+    fn d06p1_kerned_overflow() {
+        // Read as one number the 20 columns have 40 digits, but the separate races are fine
+        let input = format!(
+            "Time: {}\nDistance: {}",
+            ["10"; 20].join(" "),
+            ["9"; 20].join(" ")
+        );
+
+        assert_eq!(part1(&input), 7u128.pow(20));
+        assert!(matches!(
+            parse_race_sheet(&input)
+                .expect("should parse input")
+                .kerned_race,
+            Err(RaceSheetError::Overflow { row: "Time", .. })
+        ));
+    }
+
+    #[test]
+    fn d06p1_winning_intervals() {
+        let race = |time, distance| Race { time, distance };
+
+        assert_eq!(race(7, 9).winning_interval(), Some(2..=5));
+        assert_eq!(race(15, 40).winning_interval(), Some(4..=11));
+        assert_eq!(race(30, 200).winning_interval(), Some(11..=19));
+        assert_eq!(race(2, 1).winning_interval(), None);
+        assert_eq!(race(0, 0).winning_interval(), None);
+    }
+
+    #[test]
+    fn d06p1_winning_interval_beyond_u64() {
+        // time^2 does not fit into u128, so the bisection path is taken
+        let race = Race {
+            time: 1 << 65,
+            distance: u128::MAX,
+        };
+        assert_eq!(race.winning_interval(), Some(1 << 64..=1 << 64));
+
+        let race = Race {
+            time: u128::MAX,
+            distance: 0,
+        };
+        assert_eq!(race.ways_to_win(), u128::MAX - 1);
+    }
 }
@@ -1,8 +1,6 @@
 use std::time::Instant;
 
-use crate::parser::parse_race_part2;
-
-mod parser;
+use day06::parser::parse_race_sheet;
 
 fn main() {
     let start_time = Instant::now();
@@ -12,19 +10,19 @@ fn main() {
     println!("[{execution_time:?} seconds] {solution}");
 }
 
-fn part2(input: &str) -> u64 {
-    let (_, race) = parse_race_part2(input).expect("should parse input");
+fn part2(input: &str) -> u128 {
+    let race = parse_race_sheet(input)
+        .expect("should parse input")
+        .kerned_race
+        .expect("kerned values should fit into u128");
 
-    (1..race.time)
-        .filter(|speed| {
-            let distance = speed * (race.time - speed);
-            distance > race.distance
-        })
-        .count() as u64
+    race.ways_to_win()
 }
 
 #[cfg(test)]
 mod tests {
+    use day06::parser::RaceSheetError;
+
     use super::*;
 
     #[test]
@@ -34,4 +32,21 @@ Distance:  9  40  200";
 
         assert_eq!(part2(input), 71503);
     }
+
+    #[test]
+    fn d06p2_kerned_overflow() {
+        let input = "Time:      7  15   30
+Distance:  9  40  200000000000000000000000000000000000000";
+
+        assert_eq!(
+            parse_race_sheet(input)
+                .expect("should parse input")
+                .kerned_race
+                .unwrap_err(),
+            RaceSheetError::Overflow {
+                row: "Distance",
+                digits: "940200000000000000000000000000000000000000".to_string()
+            }
+        );
+    }
 }
@@ -0,0 +1,141 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::parser::{parse_input, Hand, HandType};
+use crate::ruleset::Ruleset;
+
+/// How a single hand was classified and ranked under a ruleset.
+#[derive(Debug)]
+pub struct HandReport {
+    pub cards: String,
+    pub hand_type: HandType,
+    /// The hand with every wild card replaced by the card it acts as, if the hand has wild cards.
+    pub best_substitution: Option<String>,
+    pub rank: u64,
+    pub bid: u64,
+}
+
+/// A hand whose rank differs between two rulesets.
+#[derive(Debug)]
+pub struct RankChange {
+    pub before: HandReport,
+    pub after: HandReport,
+}
+
+impl HandReport {
+    pub fn winnings(&self) -> u64 {
+        self.bid * self.rank
+    }
+}
+
+impl Display for HandReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let substitution = self
+            .best_substitution
+            .as_ref()
+            .map(|cards| format!("as {cards}"))
+            .unwrap_or_default();
+
+        write!(
+            f,
+            "#{:<5} {} {:<8} {:<12} {:>5} x {:<5} = {}",
+            self.rank,
+            self.cards,
+            substitution,
+            self.hand_type,
+            self.bid,
+            self.rank,
+            self.winnings()
+        )
+    }
+}
+
+impl Display for RankChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} #{} -> {} #{} ({:+})",
+            self.after.cards,
+            self.before.hand_type,
+            self.before.rank,
+            self.after.hand_type,
+            self.after.rank,
+            self.after.rank as i64 - self.before.rank as i64
+        )
+    }
+}
+
+/// Explains every hand of the input under the ruleset, ordered from the weakest to the strongest.
+pub fn explain(input: &str, ruleset: &Ruleset) -> Vec<HandReport> {
+    let mut reports = reports_in_input_order(input, ruleset);
+    reports.sort_by_key(|report| report.rank);
+    reports
+}
+
+/// Lists the hands that change rank when switching from one ruleset to another,
+/// ordered by their rank under the second ruleset.
+pub fn diff(input: &str, before: &Ruleset, after: &Ruleset) -> Vec<RankChange> {
+    let mut changes: Vec<RankChange> = reports_in_input_order(input, before)
+        .into_iter()
+        .zip(reports_in_input_order(input, after))
+        .filter(|(before, after)| before.rank != after.rank)
+        .map(|(before, after)| RankChange { before, after })
+        .collect();
+    changes.sort_by_key(|change| change.after.rank);
+    changes
+}
+
+fn reports_in_input_order(input: &str, ruleset: &Ruleset) -> Vec<HandReport> {
+    let hands = parse_input(input, ruleset);
+
+    let mut order: Vec<usize> = (0..hands.len()).collect();
+    order.sort_by(|&a, &b| ruleset.compare(&hands[a], &hands[b]));
+    let mut ranks = vec![0; hands.len()];
+    for (idx, hand_idx) in order.into_iter().enumerate() {
+        ranks[hand_idx] = idx as u64 + 1;
+    }
+
+    hands
+        .iter()
+        .zip(ranks)
+        .map(|(hand, rank)| HandReport {
+            cards: hand.cards.iter().map(|card| card.label).collect(),
+            hand_type: hand.hand_type,
+            best_substitution: best_substitution(hand, ruleset),
+            rank,
+            bid: hand.bid,
+        })
+        .collect()
+}
+
+// Wild cards always join the largest group of natural cards, preferring the strongest card on ties.
+// A hand made only of wild cards becomes five of the strongest natural card of the ruleset.
+fn best_substitution(hand: &Hand, ruleset: &Ruleset) -> Option<String> {
+    if !hand.cards.iter().any(|card| card.wild) {
+        return None;
+    }
+
+    let target = hand
+        .cards
+        .iter()
+        .filter(|card| !card.wild)
+        .max_by_key(|card| {
+            let group_size = hand.cards.iter().filter(|other| other == card).count();
+            (group_size, card.strength)
+        })
+        .map(|card| card.label)
+        .or_else(|| {
+            ruleset
+                .card_order
+                .chars()
+                .rev()
+                .find(|&label| !ruleset.wild_cards.contains(label))
+        })?;
+
+    Some(
+        hand.cards
+            .iter()
+            .map(|card| if card.wild { target } else { card.label })
+            .collect(),
+    )
+}
@@ -0,0 +1,3 @@
+pub mod explain;
+pub mod parser;
+pub mod ruleset;
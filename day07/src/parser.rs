@@ -1,6 +1,7 @@
-use std::ops::Deref;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 
-use itertools::Itertools;
+use crate::ruleset::Ruleset;
 
 #[derive(Debug)]
 pub struct Hand {
@@ -9,25 +10,15 @@ pub struct Hand {
     pub bid: u64,
 }
 
-#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Clone)]
-pub enum Card {
-    Joker = 1,
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    Eight,
-    Nine,
-    Ten,
-    Jack,
-    Queen,
-    King,
-    Ace,
+/// A card as seen by a particular ruleset. Cards are ordered by their strength in that ruleset.
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct Card {
+    pub strength: u8,
+    pub label: char,
+    pub wild: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum HandType {
     HighCard,
     OnePair,
@@ -39,8 +30,8 @@ pub enum HandType {
 }
 
 impl Hand {
-    pub fn new_part1(cards: Vec<Card>, bid: u64) -> Self {
-        let hand_type = Self::get_hand_type_part1(&cards);
+    pub fn new(cards: Vec<Card>, bid: u64) -> Self {
+        let hand_type = Self::get_hand_type(&cards);
         Hand {
             cards,
             hand_type,
@@ -48,138 +39,78 @@ impl Hand {
         }
     }
 
-    pub fn new_part2(cards: Vec<Card>, bid: u64) -> Self {
-        let hand_type = Self::get_hand_type_part2(&cards);
-        Hand {
-            cards,
-            hand_type,
-            bid,
-        }
-    }
-
-    fn get_hand_type_part1(cards: &Vec<Card>) -> HandType {
-        let card_counts = cards.iter().counts().values().sorted().join("|");
-        match card_counts.deref() {
-            "1" => HandType::HighCard,
-            "2" => HandType::OnePair,
-            "3" => HandType::ThreeOfAKind,
-            "4" => HandType::FourOfAKind,
-            "5" => HandType::FiveOfAKind,
-            "1|1" => HandType::HighCard,
-            "1|2" => HandType::OnePair,
-            "1|3" => HandType::ThreeOfAKind,
-            "1|4" => HandType::FourOfAKind,
-            "2|2" => HandType::TwoPair,
-            "2|3" => HandType::FullHouse,
-            "1|1|3" => HandType::ThreeOfAKind,
-            "1|2|2" => HandType::TwoPair,
-            "1|1|2" => HandType::OnePair,
-            "1|1|1" => HandType::HighCard,
-            "1|1|1|1" => HandType::HighCard,
-            "1|1|1|2" => HandType::OnePair,
-            "1|1|1|1|1" => HandType::HighCard,
-            val => panic!("unable to get hand type. Encountered: {}", val),
+    // Classifies the hand from the sizes of its two largest groups of matching cards, with all wild
+    // cards joining the largest group. Counting happens in a fixed array indexed by card strength,
+    // so this works for any hand size without allocating.
+    fn get_hand_type(cards: &[Card]) -> HandType {
+        let mut counts = [0usize; u8::MAX as usize + 1];
+        let mut wild_count = 0;
+        for card in cards {
+            if card.wild {
+                wild_count += 1;
+            } else {
+                counts[card.strength as usize] += 1;
+            }
         }
-    }
-
-    fn get_hand_type_part2(cards: &Vec<Card>) -> HandType {
-        let cards_without_joker: Vec<Card> = cards
-            .iter()
-            .filter(|&card| *card != Card::Joker)
-            .cloned()
-            .collect();
-        let jokers_count = cards.len() - cards_without_joker.len();
 
-        if jokers_count == 5usize || jokers_count == 4usize {
-            return HandType::FiveOfAKind;
+        let (mut largest, mut second) = (0, 0);
+        for count in counts {
+            if count > largest {
+                second = largest;
+                largest = count;
+            } else if count > second {
+                second = count;
+            }
         }
 
-        let hand_type_no_joker = Hand::get_hand_type_part1(&cards_without_joker);
-
-        match jokers_count {
-            3usize => match hand_type_no_joker {
-                HandType::OnePair => HandType::FiveOfAKind,
-                HandType::HighCard => HandType::FourOfAKind,
-                _ => panic!("unable to determine hand type with 3 jokers"),
-            },
-            2usize => match hand_type_no_joker {
-                HandType::ThreeOfAKind => HandType::FiveOfAKind,
-                HandType::OnePair => HandType::FourOfAKind,
-                HandType::HighCard => HandType::ThreeOfAKind,
-                _ => panic!("unable to determine hand type with 2 jokers"),
-            },
-            1usize => match hand_type_no_joker {
-                HandType::FourOfAKind => HandType::FiveOfAKind,
-                HandType::ThreeOfAKind => HandType::FourOfAKind,
-                HandType::TwoPair => HandType::FullHouse,
-                HandType::OnePair => HandType::ThreeOfAKind,
-                HandType::HighCard => HandType::OnePair,
-                _ => panic!("unable to determine hand type with 1 joker"),
-            },
-            0usize => hand_type_no_joker,
-            _ => panic!("unable to determine hand type with jokers"),
-        }
+        HandType::from_group_sizes(largest + wild_count, second)
     }
 }
 
-pub fn parse_input_part1(input: &str) -> Vec<Hand> {
-    input.lines().map(parse_hand_part1).collect()
+impl HandType {
+    /// Returns the hand type for the given sizes of the largest and the second-largest group of
+    /// matching cards. Groups larger than five still count as five of a kind.
+    pub fn from_group_sizes(largest: usize, second: usize) -> Self {
+        match (largest, second) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2..) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
 }
 
-pub fn parse_input_part2(input: &str) -> Vec<Hand> {
-    input.lines().map(parse_hand_part2).collect()
+impl Display for HandType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            HandType::HighCard => "HighCard",
+            HandType::OnePair => "OnePair",
+            HandType::TwoPair => "TwoPair",
+            HandType::ThreeOfAKind => "ThreeOfAKind",
+            HandType::FullHouse => "FullHouse",
+            HandType::FourOfAKind => "FourOfAKind",
+            HandType::FiveOfAKind => "FiveOfAKind",
+        })
+    }
 }
 
-fn parse_hand_part1(input: &str) -> Hand {
-    let parts: Vec<&str> = input.split_whitespace().collect();
-    let cards_str = parts[0];
-    let bid: u64 = parts[1].parse().expect("should parse bid");
-    let cards: Vec<Card> = cards_str.chars().map(parse_card_rank_part1).collect();
-    Hand::new_part1(cards, bid)
+pub fn parse_input(input: &str, ruleset: &Ruleset) -> Vec<Hand> {
+    input
+        .lines()
+        .map(|line| parse_hand(line, ruleset))
+        .collect()
 }
 
-fn parse_hand_part2(input: &str) -> Hand {
+fn parse_hand(input: &str, ruleset: &Ruleset) -> Hand {
     let parts: Vec<&str> = input.split_whitespace().collect();
     let cards_str = parts[0];
     let bid: u64 = parts[1].parse().expect("should parse bid");
-    let cards: Vec<Card> = cards_str.chars().map(parse_card_rank_part2).collect();
-    Hand::new_part2(cards, bid)
-}
-
-fn parse_card_rank_part1(card: char) -> Card {
-    match card {
-        'A' => Card::Ace,
-        'K' => Card::King,
-        'Q' => Card::Queen,
-        'J' => Card::Jack,
-        'T' => Card::Ten,
-        '9' => Card::Nine,
-        '8' => Card::Eight,
-        '7' => Card::Seven,
-        '6' => Card::Six,
-        '5' => Card::Five,
-        '4' => Card::Four,
-        '3' => Card::Three,
-        '2' => Card::Two,
-        _ => panic!("invalid card rank encountered"),
-    }
-}
-
-fn parse_card_rank_part2(card: char) -> Card {
-    match card {
-        'A' => Card::Ace,
-        'K' => Card::King,
-        'Q' => Card::Queen,
-        'T' => Card::Ten,
-        '9' => Card::Nine,
-        '8' => Card::Eight,
-        '7' => Card::Seven,
-        '6' => Card::Six,
-        '5' => Card::Five,
-        '4' => Card::Four,
-        '3' => Card::Three,
-        '2' => Card::Two,
-        'J' => Card::Joker,
-        _ => panic!("invalid card rank encountered"),
-    }
+    let cards: Vec<Card> = cards_str
+        .chars()
+        .map(|label| ruleset.card(label).expect("invalid card rank encountered"))
+        .collect();
+    Hand::new(cards, bid)
 }
@@ -1,31 +1,32 @@
+use std::env;
 use std::time::Instant;
 
-use crate::parser::parse_input_part1;
-
-mod parser;
+use day07::explain::explain;
+use day07::parser::parse_input;
+use day07::ruleset::STANDARD;
 
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p1.txt");
+    if env::args().any(|arg| arg == "--explain") {
+        explain(input_file, &STANDARD)
+            .iter()
+            .for_each(|report| println!("{report}"));
+    }
     let solution = part1(input_file);
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
 
 fn part1(input: &str) -> u64 {
-    let mut hands = parse_input_part1(input);
-
-    hands.sort_by(|a, b| {
-        b.hand_type
-            .cmp(&a.hand_type)
-            .then_with(|| b.cards.iter().cmp(a.cards.iter()))
-    });
+    let mut hands = parse_input(input, &STANDARD);
+    STANDARD.rank(&mut hands);
 
     hands
         .iter()
         .enumerate()
         .map(|(idx, hand)| {
-            let rank = (hands.len() - idx) as u64;
+            let rank = idx as u64 + 1;
             hand.bid * rank
         })
         .sum()
@@ -33,6 +34,8 @@ fn part1(input: &str) -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use day07::ruleset::{Ruleset, TieBreak};
+
     use super::*;
 
     #[test]
@@ -45,4 +48,53 @@ QQQJA 483";
 
         assert_eq!(part1(input), 6440);
     }
+
+    #[test]
+    fn d07p1_custom_ruleset() {
+        const ACES_LOW_POKER: Ruleset = Ruleset {
+            card_order: "A23456789TJQK",
+            wild_cards: "",
+            tie_break: TieBreak::StrongestFirst,
+        };
+        let input = "2345K 1
+Q3456 2
+AAKKQ 3
+QQKKA 4";
+
+        let mut hands = parse_input(input, &ACES_LOW_POKER);
+        ACES_LOW_POKER.rank(&mut hands);
+        let bids: Vec<u64> = hands.iter().map(|hand| hand.bid).collect();
+        assert_eq!(bids, vec![2, 1, 3, 4]);
+
+        let mut hands = parse_input(input, &STANDARD);
+        STANDARD.rank(&mut hands);
+        let bids: Vec<u64> = hands.iter().map(|hand| hand.bid).collect();
+        assert_eq!(bids, vec![1, 2, 4, 3]);
+
+        // The pair decides before the kickers, so 33 beats 22 despite the king
+        let mut hands = parse_input("22AKQ 1\n33JT9 2", &ACES_LOW_POKER);
+        ACES_LOW_POKER.rank(&mut hands);
+        let bids: Vec<u64> = hands.iter().map(|hand| hand.bid).collect();
+        assert_eq!(bids, vec![1, 2]);
+    }
+
+    #[test]
+    fn d07p1_card_order_limit() {
+        let card_order: String = (0..300)
+            .map(|offset| char::from_u32(0x100 + offset).expect("should be a valid char"))
+            .collect();
+        let ruleset = Ruleset {
+            card_order: card_order.leak(),
+            wild_cards: "",
+            tie_break: TieBreak::DealtOrder,
+        };
+
+        let last_fitting = char::from_u32(0x100 + 255).expect("should be a valid char");
+        let first_too_many = char::from_u32(0x100 + 256).expect("should be a valid char");
+        assert_eq!(
+            ruleset.card(last_fitting).map(|card| card.strength),
+            Some(255)
+        );
+        assert_eq!(ruleset.card(first_too_many), None);
+    }
 }
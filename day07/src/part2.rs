@@ -1,31 +1,37 @@
+use std::env;
 use std::time::Instant;
 
-use crate::parser::parse_input_part2;
-
-mod parser;
+use day07::explain::{diff, explain};
+use day07::parser::parse_input;
+use day07::ruleset::{JOKER, STANDARD};
 
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p2.txt");
+    if env::args().any(|arg| arg == "--explain") {
+        explain(input_file, &JOKER)
+            .iter()
+            .for_each(|report| println!("{report}"));
+    }
+    if env::args().any(|arg| arg == "--diff") {
+        diff(input_file, &STANDARD, &JOKER)
+            .iter()
+            .for_each(|change| println!("{change}"));
+    }
     let solution = part2(input_file);
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
 
 fn part2(input: &str) -> u64 {
-    let mut hands = parse_input_part2(input);
-
-    hands.sort_by(|a, b| {
-        b.hand_type
-            .cmp(&a.hand_type)
-            .then_with(|| b.cards.iter().cmp(a.cards.iter()))
-    });
+    let mut hands = parse_input(input, &JOKER);
+    JOKER.rank(&mut hands);
 
     hands
         .iter()
         .enumerate()
         .map(|(idx, hand)| {
-            let rank = (hands.len() - idx) as u64;
+            let rank = idx as u64 + 1;
             hand.bid * rank
         })
         .sum()
@@ -33,6 +39,10 @@ fn part2(input: &str) -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use day07::explain::HandReport;
+    use day07::parser::HandType;
+    use day07::ruleset::{Ruleset, TieBreak};
+
     use super::*;
 
     #[test]
@@ -45,4 +55,87 @@ QQQJA 483";
 
         assert_eq!(part2(input), 5905);
     }
+
+    #[test]
+    fn d07p2_explain() {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+        let reports = explain(input, &JOKER);
+        let strongest = reports.last().expect("should explain every hand");
+        assert_eq!(strongest.cards, "KTJJT");
+        assert_eq!(strongest.hand_type, HandType::FourOfAKind);
+        assert_eq!(strongest.best_substitution.as_deref(), Some("KTTTT"));
+        assert_eq!(strongest.winnings(), 1100);
+        assert_eq!(
+            reports.iter().map(HandReport::winnings).sum::<u64>(),
+            part2(input)
+        );
+
+        let changes: Vec<(String, u64, u64)> = diff(input, &STANDARD, &JOKER)
+            .into_iter()
+            .map(|change| (change.after.cards, change.before.rank, change.after.rank))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("KK677".to_string(), 3, 2),
+                ("T55J5".to_string(), 4, 3),
+                ("QQQJA".to_string(), 5, 4),
+                ("KTJJT".to_string(), 2, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn d07p2_multiple_wild_ranks() {
+        const DEUCES_AND_JOKERS_WILD: Ruleset = Ruleset {
+            card_order: "J23456789TQKA",
+            wild_cards: "J2",
+            tie_break: TieBreak::DealtOrder,
+        };
+        let input = "2J2JA 1
+23456 2
+J2345 3";
+
+        let hand_types: Vec<HandType> = parse_input(input, &DEUCES_AND_JOKERS_WILD)
+            .into_iter()
+            .map(|hand| hand.hand_type)
+            .collect();
+        assert_eq!(
+            hand_types,
+            vec![
+                HandType::FiveOfAKind,
+                HandType::OnePair,
+                HandType::ThreeOfAKind
+            ]
+        );
+    }
+
+    #[test]
+    fn d07p2_any_hand_size() {
+        let input = "JJJJJJJ 1
+AK 2
+AJ 3
+KKQQJ2 4
+KKKQQQ2 5";
+
+        let hand_types: Vec<HandType> = parse_input(input, &JOKER)
+            .into_iter()
+            .map(|hand| hand.hand_type)
+            .collect();
+        assert_eq!(
+            hand_types,
+            vec![
+                HandType::FiveOfAKind,
+                HandType::HighCard,
+                HandType::OnePair,
+                HandType::FullHouse,
+                HandType::FullHouse
+            ]
+        );
+    }
 }
@@ -0,0 +1,77 @@
+use std::cmp::{Ordering, Reverse};
+
+use itertools::Itertools;
+
+use crate::parser::{Card, Hand};
+
+/// Order in which the cards of two hands of the same type are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the cards in the order they were dealt, as Camel Cards does.
+    DealtOrder,
+    /// Compare the largest groups of equal cards first, stronger groups before weaker ones of the
+    /// same size, and then the remaining cards from the strongest down, as poker does.
+    StrongestFirst,
+}
+
+/// The rules of a Camel Cards variant, defined as data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ruleset {
+    /// Card labels ordered from the weakest to the strongest.
+    pub card_order: &'static str,
+    /// Card labels that act like whatever card makes the strongest hand type.
+    pub wild_cards: &'static str,
+    pub tie_break: TieBreak,
+}
+
+/// Part 1 rules: no wild cards, `J` is a Jack.
+pub const STANDARD: Ruleset = Ruleset {
+    card_order: "23456789TJQKA",
+    wild_cards: "",
+    tie_break: TieBreak::DealtOrder,
+};
+
+/// Part 2 rules: `J` is a Joker, the weakest card, which acts as a wild card.
+pub const JOKER: Ruleset = Ruleset {
+    card_order: "J23456789TQKA",
+    wild_cards: "J",
+    tie_break: TieBreak::DealtOrder,
+};
+
+impl Ruleset {
+    /// Returns the card with the given label under this ruleset, or `None` if the label is not
+    /// part of the card order or comes after the 256th label, where strengths no longer fit u8.
+    pub fn card(&self, label: char) -> Option<Card> {
+        let strength = self.card_order.chars().position(|c| c == label)?;
+
+        Some(Card {
+            strength: u8::try_from(strength).ok()?,
+            label,
+            wild: self.wild_cards.contains(label),
+        })
+    }
+
+    /// Compares two hands by their type first and then card by card using the tie-break order.
+    pub fn compare(&self, a: &Hand, b: &Hand) -> Ordering {
+        a.hand_type
+            .cmp(&b.hand_type)
+            .then_with(|| match self.tie_break {
+                TieBreak::DealtOrder => a.cards.iter().cmp(b.cards.iter()),
+                TieBreak::StrongestFirst => poker_order(&a.cards).cmp(poker_order(&b.cards)),
+            })
+    }
+
+    /// Sorts hands from the weakest to the strongest, so that the rank of a hand is its index + 1.
+    pub fn rank(&self, hands: &mut [Hand]) {
+        hands.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+/// Orders the cards by how often they appear and then by strength, both descending, so a pair
+/// comes before any single card and the kickers come last.
+fn poker_order(cards: &[Card]) -> impl Iterator<Item = &Card> {
+    let counts = cards.iter().counts_by(|card| card.strength);
+    cards
+        .iter()
+        .sorted_by_key(|card| Reverse((counts[&card.strength], card.strength)))
+}
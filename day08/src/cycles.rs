@@ -0,0 +1,133 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use common::number_theory::{crt, NumberTheoryError};
+
+use crate::parser::{Instruction, Network, NodeId};
+
+/// The loop structure of a single ghost walking the network from one start node.
+///
+/// The walk is a sequence of `(node, instruction index)` states, so it must eventually repeat.
+/// Steps are counted from 0, the step at which the ghost stands on its start node.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GhostCycle {
+    /// Number of steps taken before the ghost enters its loop.
+    pub tail_length: usize,
+    /// Number of steps it takes to walk the loop once.
+    pub cycle_length: usize,
+    /// Steps at which the ghost stands on a `..Z` node before entering its loop.
+    pub tail_z_steps: Vec<usize>,
+    /// Steps at which the ghost stands on a `..Z` node during its first walk of the loop.
+    /// Every such step repeats after each further `cycle_length` steps.
+    pub cycle_z_steps: Vec<usize>,
+}
+
+impl GhostCycle {
+    /// Walks the network from `start` until a `(node, instruction index)` state repeats.
+    pub fn detect(start: NodeId, instructions: &[Instruction], network: &Network) -> Self {
+        // First step at which each state was seen, indexed by node * instructions + instruction
+        let mut seen_states = vec![usize::MAX; network.len() * instructions.len()];
+        let mut z_steps = Vec::new();
+        let mut current_node = start;
+        let mut step = 0;
+
+        let loop_start = loop {
+            let instruction_idx = step % instructions.len();
+            let state = current_node as usize * instructions.len() + instruction_idx;
+            if seen_states[state] != usize::MAX {
+                break seen_states[state];
+            }
+            seen_states[state] = step;
+
+            if network.is_end(current_node) {
+                z_steps.push(step);
+            }
+
+            current_node = network.next(current_node, instructions[instruction_idx]);
+            step += 1;
+        };
+
+        let (tail_z_steps, cycle_z_steps) = z_steps.into_iter().partition(|&z| z < loop_start);
+
+        GhostCycle {
+            tail_length: loop_start,
+            cycle_length: step - loop_start,
+            tail_z_steps,
+            cycle_z_steps,
+        }
+    }
+
+    /// Checks whether the ghost stands on a `..Z` node after the given number of steps.
+    pub fn is_z_step(&self, step: usize) -> bool {
+        if step < self.tail_length {
+            self.tail_z_steps.binary_search(&step).is_ok()
+        } else {
+            let step_in_cycle = self.tail_length + (step - self.tail_length) % self.cycle_length;
+            self.cycle_z_steps.binary_search(&step_in_cycle).is_ok()
+        }
+    }
+}
+
+impl Display for GhostCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tail {}, cycle {}, ..Z steps before the loop {:?}, ..Z steps in the loop {:?}",
+            self.tail_length, self.cycle_length, self.tail_z_steps, self.cycle_z_steps
+        )
+    }
+}
+
+/// Finds the first step at which every ghost stands on a `..Z` node, if there is one.
+///
+/// Until the last ghost enters its loop the ghosts are simulated step by step. After that every
+/// ghost is periodic, so each choice of one `..Z` step per loop is a system of congruences, which is
+/// solved with the Chinese Remainder Theorem generalized to moduli that are not coprime.
+///
+/// Fails only if the loop lengths are so large that the answer would overflow `u128`.
+pub fn first_common_z_step(cycles: &[GhostCycle]) -> Result<Option<u128>, NumberTheoryError> {
+    let Some(all_in_loops_from) = cycles.iter().map(|cycle| cycle.tail_length).max() else {
+        return Ok(None);
+    };
+
+    if let Some(step) =
+        (0..all_in_loops_from).find(|&step| cycles.iter().all(|cycle| cycle.is_z_step(step)))
+    {
+        return Ok(Some(step as u128));
+    }
+
+    let mut congruences: Vec<(u128, u128)> = vec![(0, 1)];
+    for cycle in cycles {
+        let modulus = cycle.cycle_length as u128;
+        let mut merged = Vec::new();
+        for &congruence in &congruences {
+            for &z_step in &cycle.cycle_z_steps {
+                match crt(&[congruence, (z_step as u128, modulus)]) {
+                    Ok(solution) => merged.push(solution),
+                    Err(NumberTheoryError::NoSolution) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+        congruences = merged;
+        congruences.sort_unstable();
+        congruences.dedup();
+    }
+
+    let all_in_loops_from = all_in_loops_from as u128;
+    let first_steps = congruences
+        .into_iter()
+        .map(|(remainder, modulus)| {
+            if remainder >= all_in_loops_from {
+                return Ok(remainder);
+            }
+            (all_in_loops_from - remainder)
+                .div_ceil(modulus)
+                .checked_mul(modulus)
+                .and_then(|offset| offset.checked_add(remainder))
+                .ok_or(NumberTheoryError::Overflow)
+        })
+        .collect::<Result<Vec<u128>, NumberTheoryError>>()?;
+
+    Ok(first_steps.into_iter().min())
+}
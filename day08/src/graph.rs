@@ -0,0 +1,124 @@
+use std::fmt::Write;
+
+use crate::parser::{Network, NodeId};
+
+/// Renders the network as a Graphviz DOT digraph with `L`/`R` edge labels.
+///
+/// Start nodes (`..A`) are drawn as boxes and end nodes (`..Z`) as double circles.
+/// Nodes whose left and right neighbours are the same get a single `L/R` edge.
+pub fn to_dot(network: &Network) -> String {
+    let mut dot = String::from("digraph network {\n");
+
+    for id in network.ids() {
+        let node = network.name(id);
+        let [left, right] = network
+            .neighbours(id)
+            .map(|neighbour| network.name(neighbour));
+
+        if network.is_start(id) {
+            writeln!(dot, "    \"{node}\" [shape=box];").expect("should write to string");
+        } else if network.is_end(id) {
+            writeln!(dot, "    \"{node}\" [shape=doublecircle];").expect("should write to string");
+        }
+
+        if left == right {
+            writeln!(dot, "    \"{node}\" -> \"{left}\" [label=\"L/R\"];")
+                .expect("should write to string");
+        } else {
+            writeln!(dot, "    \"{node}\" -> \"{left}\" [label=\"L\"];")
+                .expect("should write to string");
+            writeln!(dot, "    \"{node}\" -> \"{right}\" [label=\"R\"];")
+                .expect("should write to string");
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Splits the network into strongly connected components using Kosaraju's algorithm.
+///
+/// Both depth-first searches use an explicit stack, so long chains of nodes can't overflow the
+/// call stack. Nodes within a component are sorted, and components are sorted by their first node.
+pub fn strongly_connected_components<'a>(network: &Network<'a>) -> Vec<Vec<&'a str>> {
+    let mut reversed: Vec<Vec<NodeId>> = vec![vec![]; network.len()];
+    for id in network.ids() {
+        for neighbour in network.neighbours(id) {
+            reversed[neighbour as usize].push(id);
+        }
+    }
+
+    // First pass: order the nodes by the time their depth-first search finishes
+    let mut visited = vec![false; network.len()];
+    let mut finish_order: Vec<NodeId> = Vec::with_capacity(network.len());
+    for root in network.ids() {
+        if visited[root as usize] {
+            continue;
+        }
+        visited[root as usize] = true;
+
+        let mut stack: Vec<(NodeId, usize)> = vec![(root, 0)];
+        while let Some((id, next_neighbour)) = stack.pop() {
+            match network.neighbours(id).get(next_neighbour) {
+                Some(&neighbour) => {
+                    stack.push((id, next_neighbour + 1));
+                    if !visited[neighbour as usize] {
+                        visited[neighbour as usize] = true;
+                        stack.push((neighbour, 0));
+                    }
+                }
+                None => finish_order.push(id),
+            }
+        }
+    }
+
+    // Second pass: every search in the reversed network started from the node that finished last
+    // collects exactly one component
+    let mut assigned = vec![false; network.len()];
+    let mut components: Vec<Vec<&str>> = Vec::new();
+    for &root in finish_order.iter().rev() {
+        if assigned[root as usize] {
+            continue;
+        }
+        assigned[root as usize] = true;
+
+        let mut component = vec![];
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            component.push(network.name(id));
+            for &predecessor in &reversed[id as usize] {
+                if !assigned[predecessor as usize] {
+                    assigned[predecessor as usize] = true;
+                    stack.push(predecessor);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    components.sort_unstable();
+    components
+}
+
+/// Returns the nodes of the network that no ghost can reach from any `..A` start node.
+pub fn unreachable_from_starts<'a>(network: &Network<'a>) -> Vec<&'a str> {
+    let mut reached = vec![false; network.len()];
+    let mut stack: Vec<NodeId> = network.ids().filter(|&id| network.is_start(id)).collect();
+    stack.iter().for_each(|&id| reached[id as usize] = true);
+
+    while let Some(id) = stack.pop() {
+        for neighbour in network.neighbours(id) {
+            if !reached[neighbour as usize] {
+                reached[neighbour as usize] = true;
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    network
+        .ids()
+        .filter(|&id| !reached[id as usize])
+        .map(|id| network.name(id))
+        .collect()
+}
@@ -0,0 +1,59 @@
+use crate::parser::{Instruction, Network, NodeId};
+
+/// Advances nodes by whole rounds of the instruction list without walking every step.
+///
+/// Level `k` maps every node to the node reached after `2^k` full rounds of instructions,
+/// so any number of rounds takes at most one lookup per bit of that number.
+#[derive(Debug)]
+pub struct JumpTable<'n> {
+    network: &'n Network<'n>,
+    instructions: &'n [Instruction],
+    levels: Vec<Vec<NodeId>>,
+}
+
+impl<'n> JumpTable<'n> {
+    pub fn new(network: &'n Network<'n>, instructions: &'n [Instruction]) -> Self {
+        let one_round: Vec<NodeId> = network
+            .ids()
+            .map(|id| {
+                instructions
+                    .iter()
+                    .fold(id, |node, &instruction| network.next(node, instruction))
+            })
+            .collect();
+
+        let mut levels = vec![one_round];
+        while levels.len() < u64::BITS as usize {
+            let previous = levels.last().expect("should have at least one level");
+            let doubled = previous.iter().map(|&id| previous[id as usize]).collect();
+            levels.push(doubled);
+        }
+
+        JumpTable {
+            network,
+            instructions,
+            levels,
+        }
+    }
+
+    /// Returns the node reached from `start` after the given number of full instruction rounds.
+    pub fn after_rounds(&self, start: NodeId, rounds: u64) -> NodeId {
+        self.levels
+            .iter()
+            .enumerate()
+            .filter(|(level, _)| rounds >> level & 1 == 1)
+            .fold(start, |node, (_, jumps)| jumps[node as usize])
+    }
+
+    /// Returns the node reached from `start` after the given number of steps.
+    pub fn after_steps(&self, start: NodeId, steps: u64) -> NodeId {
+        let round_length = self.instructions.len() as u64;
+        let node = self.after_rounds(start, steps / round_length);
+
+        self.instructions[..(steps % round_length) as usize]
+            .iter()
+            .fold(node, |node, &instruction| {
+                self.network.next(node, instruction)
+            })
+    }
+}
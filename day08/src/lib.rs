@@ -0,0 +1,4 @@
+pub mod cycles;
+pub mod graph;
+pub mod jump_table;
+pub mod parser;
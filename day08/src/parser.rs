@@ -1,46 +1,147 @@
 use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete;
 use nom::character::complete::{alphanumeric1, line_ending, multispace1};
 use nom::combinator::eof;
-use nom::multi::{fold_many1, many1};
+use nom::multi::many1;
 use nom::sequence::{delimited, separated_pair, terminated};
 use nom::{IResult, Parser};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum Instruction {
-    Left,
-    Right,
+    Left = 0,
+    Right = 1,
 }
 
-pub fn parse_input(input: &str) -> IResult<&str, (Vec<Instruction>, BTreeMap<&str, (&str, &str)>)> {
+/// Dense id of a node, assigned in the alphabetical order of the node names.
+pub type NodeId = u32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NetworkError {
+    Syntax(String),
+    UndefinedNode(String),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Syntax(error) => write!(f, "unable to parse network: {error}"),
+            NetworkError::UndefinedNode(name) => {
+                write!(f, "node {name} is referenced but never defined")
+            }
+        }
+    }
+}
+
+impl Error for NetworkError {}
+
+/// The node network with interned node names and a flat `[left, right]` transition table.
+#[derive(Debug)]
+pub struct Network<'a> {
+    names: Vec<&'a str>,
+    transitions: Vec<[NodeId; 2]>,
+}
+
+impl<'a> Network<'a> {
+    // Later definitions of the same node replace earlier ones
+    fn from_definitions(definitions: Vec<Definition<'a>>) -> Result<Self, NetworkError> {
+        let definitions: BTreeMap<&str, (&str, &str)> = definitions.into_iter().collect();
+        let names: Vec<&str> = definitions.keys().copied().collect();
+
+        let id_of = |name: &str| {
+            names
+                .binary_search(&name)
+                .map(|idx| idx as NodeId)
+                .map_err(|_| NetworkError::UndefinedNode(name.to_string()))
+        };
+        let transitions = definitions
+            .values()
+            .map(|&(left, right)| Ok([id_of(left)?, id_of(right)?]))
+            .collect::<Result<Vec<[NodeId; 2]>, NetworkError>>()?;
+
+        Ok(Network { names, transitions })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names
+            .binary_search(&name)
+            .ok()
+            .map(|idx| idx as NodeId)
+    }
+
+    pub fn name(&self, id: NodeId) -> &'a str {
+        self.names[id as usize]
+    }
+
+    /// Returns the ids of all nodes, in the alphabetical order of their names.
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        0..self.names.len() as NodeId
+    }
+
+    /// Returns the `[left, right]` neighbours of a node.
+    pub fn neighbours(&self, id: NodeId) -> [NodeId; 2] {
+        self.transitions[id as usize]
+    }
+
+    pub fn next(&self, id: NodeId, instruction: Instruction) -> NodeId {
+        self.transitions[id as usize][instruction as usize]
+    }
+
+    /// Checks whether the node is a ghost start node, i.e. its name ends with `A`.
+    pub fn is_start(&self, id: NodeId) -> bool {
+        self.name(id).ends_with('A')
+    }
+
+    /// Checks whether the node is a ghost end node, i.e. its name ends with `Z`.
+    pub fn is_end(&self, id: NodeId) -> bool {
+        self.name(id).ends_with('Z')
+    }
+}
+
+/// Parses the instructions and the node network.
+///
+/// Fails with [`NetworkError::UndefinedNode`] when a node is used as a destination but never
+/// defined itself.
+pub fn parse_input(input: &str) -> Result<(Vec<Instruction>, Network<'_>), NetworkError> {
+    let (_, (instructions, definitions)) =
+        parse_definitions(input).map_err(|error| NetworkError::Syntax(error.to_string()))?;
+
+    Ok((instructions, Network::from_definitions(definitions)?))
+}
+
+type Definition<'a> = (&'a str, (&'a str, &'a str));
+
+fn parse_definitions(input: &str) -> IResult<&str, (Vec<Instruction>, Vec<Definition<'_>>)> {
     let (input, instructions) = many1(alt((
         complete::char('R').map(|_| Instruction::Right),
         complete::char('L').map(|_| Instruction::Left),
     )))(input)?;
     let (input, _) = multispace1(input)?;
 
-    let (input, map) = fold_many1(
-        terminated(
-            separated_pair(
-                alphanumeric1,
-                tag(" = "),
-                delimited(
-                    complete::char('('),
-                    separated_pair(alphanumeric1, tag(", "), alphanumeric1),
-                    complete::char(')'),
-                ),
+    let (input, definitions) = many1(terminated(
+        separated_pair(
+            alphanumeric1,
+            tag(" = "),
+            delimited(
+                complete::char('('),
+                separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+                complete::char(')'),
             ),
-            alt((line_ending, eof)),
         ),
-        BTreeMap::new,
-        |mut acc: BTreeMap<&str, (&str, &str)>, (key, value)| {
-            acc.insert(key, value);
-            acc
-        },
-    )(input)?;
-
-    Ok((input, (instructions, map)))
+        alt((line_ending, eof)),
+    ))(input)?;
+
+    Ok((input, (instructions, definitions)))
 }
@@ -1,8 +1,6 @@
 use std::time::Instant;
 
-use crate::parser::{parse_input, Instruction};
-
-mod parser;
+use day08::parser::parse_input;
 
 fn main() {
     let start_time = Instant::now();
@@ -13,27 +11,17 @@ fn main() {
 }
 
 fn part1(input: &str) -> u64 {
-    let (_, (instructions, map)) = parse_input(input).expect("should parse input");
-    let mut current_node = "AAA";
+    let (instructions, network) = parse_input(input).expect("should parse input");
+    let end_node = network.id("ZZZ").expect("should find node ZZZ");
+    let mut current_node = network.id("AAA").expect("should find node AAA");
     let Some(step_count) =
         instructions
             .iter()
             .cycle()
             .enumerate()
-            .find_map(|(idx, instruction)| {
-                let directions = map
-                    .get(current_node)
-                    .expect("should get current node from map");
-                let next_node = match instruction {
-                    Instruction::Right => directions.1,
-                    Instruction::Left => directions.0,
-                };
-                if next_node == "ZZZ" {
-                    Some(idx + 1)
-                } else {
-                    current_node = next_node;
-                    None
-                }
+            .find_map(|(idx, &instruction)| {
+                current_node = network.next(current_node, instruction);
+                (current_node == end_node).then_some(idx + 1)
             })
     else {
         panic!()
@@ -1,80 +1,89 @@
+use std::env;
 use std::time::Instant;
 
-use crate::parser::{parse_input, Instruction};
-
-mod parser;
+use day08::cycles::{first_common_z_step, GhostCycle};
+use day08::graph::{strongly_connected_components, to_dot, unreachable_from_starts};
+use day08::jump_table::JumpTable;
+use day08::parser::{parse_input, NodeId};
 
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p2.txt");
+    if env::args().any(|arg| arg == "--dot") {
+        let (_, network) = parse_input(input_file).expect("should parse input");
+        print!("{}", to_dot(&network));
+        return;
+    }
+    if env::args().any(|arg| arg == "--report") {
+        print_report(input_file);
+    }
     let solution = part2(input_file);
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
 
-fn part2(input: &str) -> u64 {
-    let (_, (instructions, map)) = parse_input(input).expect("should parse input");
+fn part2(input: &str) -> u128 {
+    let (instructions, network) = parse_input(input).expect("should parse input");
 
-    let starting_nodes: Vec<&str> = map
-        .keys()
-        .filter(|key| key.ends_with('A'))
-        .cloned()
+    let cycles: Vec<GhostCycle> = network
+        .ids()
+        .filter(|&id| network.is_start(id))
+        .map(|id| GhostCycle::detect(id, &instructions, &network))
         .collect();
 
-    let results = starting_nodes
-        .iter()
-        .map(|node| {
-            let mut visited_nodes = vec![*node];
-            let mut current_node = *node;
-
-            instructions
-                .iter()
-                .cycle()
-                .enumerate()
-                .find_map(|(idx, instruction)| {
-                    let directions = map
-                        .get(current_node)
-                        .expect("should get current node from map");
-                    let next_node = match instruction {
-                        Instruction::Right => directions.1,
-                        Instruction::Left => directions.0,
-                    };
-                    if next_node.ends_with('Z') {
-                        Some(idx + 1)
-                    } else {
-                        visited_nodes.push(next_node);
-                        current_node = next_node;
-                        None
-                    }
-                })
-                .expect("should find a cycle")
-        })
-        .collect::<Vec<usize>>();
-
-    lcm(&results) as u64
+    first_common_z_step(&cycles)
+        .expect("loop lengths should not overflow u128")
+        .expect("ghosts should all stand on ..Z nodes at some step")
 }
 
-fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-
-    a * b / gcd_of_two_numbers(a, b)
-}
-
-fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
+fn print_report(input: &str) {
+    let (instructions, network) = parse_input(input).expect("should parse input");
+
+    let components = strongly_connected_components(&network);
+    let largest_component = components.iter().map(Vec::len).max().unwrap_or(0);
+    let unreachable = unreachable_from_starts(&network);
+    println!(
+        "{} nodes, {} instructions, {} strongly connected components (largest has {} nodes)",
+        network.len(),
+        instructions.len(),
+        components.len(),
+        largest_component
+    );
+    println!(
+        "{} nodes unreachable from ..A starts: {:?}",
+        unreachable.len(),
+        unreachable
+    );
+
+    let starts: Vec<NodeId> = network.ids().filter(|&id| network.is_start(id)).collect();
+    let cycles: Vec<GhostCycle> = starts
+        .iter()
+        .map(|&id| GhostCycle::detect(id, &instructions, &network))
+        .collect();
+    starts
+        .iter()
+        .zip(&cycles)
+        .for_each(|(&id, cycle)| println!("{}: {cycle}", network.name(id)));
+
+    // Cross-check the answer by jumping every ghost straight to that step
+    if let Ok(Some(step)) = first_common_z_step(&cycles) {
+        match u64::try_from(step) {
+            Ok(steps) => {
+                let jump_table = JumpTable::new(&network, &instructions);
+                let all_on_z = starts
+                    .iter()
+                    .all(|&id| network.is_end(jump_table.after_steps(id, steps)));
+                println!("all ghosts stand on ..Z nodes after {step} steps: {all_on_z}");
+            }
+            Err(_) => println!("{step} steps are too many for the jump table to check"),
+        }
     }
-
-    gcd_of_two_numbers(b, a % b)
 }
 
 #[cfg(test)]
 mod tests {
+    use day08::parser::NetworkError;
+
     use super::*;
 
     #[test]
@@ -92,4 +101,137 @@ XXX = (XXX, XXX)";
 
         assert_eq!(part2(input), 6);
     }
+
+    #[test]
+    fn d08p2_jump_table() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+        let (instructions, network) = parse_input(input).expect("should parse input");
+        let jump_table = JumpTable::new(&network, &instructions);
+
+        for start in network.ids().filter(|&id| network.is_start(id)) {
+            let mut node = start;
+            for step in 0..50 {
+                assert_eq!(jump_table.after_steps(start, step), node);
+                node = network.next(node, instructions[step as usize % instructions.len()]);
+            }
+            assert!(network.is_end(jump_table.after_steps(start, 6)));
+            assert!(network.is_end(jump_table.after_steps(start, 6_000_000_000_000)));
+        }
+    }
+
+    #[test]
+    fn d08p2_undefined_node() {
+        let error = parse_input("L\n\nAAA = (BBB, AAA)").unwrap_err();
+        assert_eq!(error, NetworkError::UndefinedNode("BBB".to_string()));
+        assert_eq!(
+            error.to_string(),
+            "node BBB is referenced but never defined"
+        );
+        assert!(matches!(
+            parse_input("L\n\nAAA = BBB"),
+            Err(NetworkError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn d08p2_network_analysis() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+QQQ = (XXX, XXX)
+XXX = (XXX, XXX)";
+
+        let (_, network) = parse_input(input).expect("should parse input");
+        assert_eq!(
+            strongly_connected_components(&network),
+            vec![
+                vec!["11A"],
+                vec!["11B", "11Z"],
+                vec!["22A"],
+                vec!["22B", "22C", "22Z"],
+                vec!["QQQ"],
+                vec!["XXX"],
+            ]
+        );
+        assert_eq!(unreachable_from_starts(&network), vec!["QQQ"]);
+
+        let dot = to_dot(&network);
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.contains("\"11A\" [shape=box];"));
+        assert!(dot.contains("\"11Z\" [shape=doublecircle];"));
+        assert!(dot.contains("\"11A\" -> \"11B\" [label=\"L\"];"));
+        assert!(dot.contains("\"11A\" -> \"XXX\" [label=\"R\"];"));
+        assert!(dot.contains("\"22B\" -> \"22C\" [label=\"L/R\"];"));
+    }
+
+    #[test]
+    fn d08p2_offset_cycles() {
+        // The first ..Z step of 11A is 1 and of 22A is 2, but 11A only returns to 11Z every
+        // 3 steps, so the least common multiple of the first ..Z steps (2) is not the answer
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+
+        let (instructions, network) = parse_input(input).expect("should parse input");
+        let id = |name| network.id(name).expect("should find node");
+        assert_eq!(
+            GhostCycle::detect(id("11A"), &instructions, &network),
+            GhostCycle {
+                tail_length: 1,
+                cycle_length: 3,
+                tail_z_steps: vec![],
+                cycle_z_steps: vec![1],
+            }
+        );
+        assert_eq!(part2(input), 4);
+    }
+
+    #[test]
+    fn d08p2_z_only_before_loop() {
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22A, 22A)";
+
+        let (instructions, network) = parse_input(input).expect("should parse input");
+        let id = |name| network.id(name).expect("should find node");
+        let cycles: Vec<GhostCycle> = ["11A", "22A"]
+            .iter()
+            .map(|&node| GhostCycle::detect(id(node), &instructions, &network))
+            .collect();
+        assert_eq!(cycles[0].tail_z_steps, vec![1]);
+        assert_eq!(first_common_z_step(&cycles), Ok(Some(1)));
+        assert_eq!(first_common_z_step(&cycles[..1]), Ok(Some(1)));
+
+        let ghosts_never_meet = [
+            GhostCycle::detect(id("22A"), &instructions, &network),
+            GhostCycle::detect(id("11Z"), &instructions, &network),
+        ];
+        assert_eq!(first_common_z_step(&ghosts_never_meet), Ok(None));
+    }
 }
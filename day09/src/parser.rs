@@ -1,10 +1,8 @@
-pub fn parse_report(input: &str) -> Vec<Vec<i64>> {
+use std::num::ParseIntError;
+
+pub fn parse_report(input: &str) -> Result<Vec<Vec<i64>>, ParseIntError> {
     input
         .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|num| num.parse::<i64>().unwrap())
-                .collect()
-        })
+        .map(|line| line.split_whitespace().map(str::parse::<i64>).collect())
         .collect()
 }
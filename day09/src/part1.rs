@@ -1,10 +1,7 @@
 use std::time::Instant;
 
-use itertools::Itertools;
-
-use crate::parser::parse_report;
-
-mod parser;
+use day09::parser::parse_report;
+use day09::sequence::Sequence;
 
 fn main() {
     let start_time = Instant::now();
@@ -15,33 +12,21 @@ fn main() {
 }
 
 fn part1(input: &str) -> i64 {
-    let report = parse_report(input);
-    report.iter().map(extrapolate_values).sum()
-}
-
-fn extrapolate_values(history: &Vec<i64>) -> i64 {
-    let mut differences = calculate_differences(history);
-    let mut difference_to_add: i64 = 0;
-
-    while !differences.iter().all(|&x| x == 0) {
-        difference_to_add += differences.last().unwrap();
-        differences = calculate_differences(&differences);
-    }
-
-    history.last().unwrap() + difference_to_add
-}
-
-fn calculate_differences(input: &[i64]) -> Vec<i64> {
-    input
+    let report = parse_report(input).expect("should parse report");
+    report
         .iter()
-        .tuple_windows()
-        .map(|(first, second)| second - first)
-        .collect()
+        .map(|history| {
+            Sequence::<i64>::new(history)
+                .and_then(|sequence| sequence.extrapolate_forward(1))
+                .expect("history should be a polynomial sequence that fits in i64")
+        })
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day09::sequence::SequenceError;
 
     #[test]
     fn d09p1_full_input() {
@@ -51,4 +36,71 @@ mod tests {
 
         assert_eq!(part1(input), 114);
     }
+
+    #[test]
+    fn d09p1_sequence() {
+        let linear = Sequence::<i64>::new(&[0, 3, 6, 9, 12, 15]).unwrap();
+        assert_eq!(linear.degree(), 1);
+        assert_eq!(linear.extrapolate_forward(3), Ok(24));
+        assert_eq!(linear.extrapolate_backward(2), Ok(-6));
+
+        let triangular = Sequence::<i64>::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(triangular.degree(), 2);
+        assert_eq!(triangular.extrapolate_forward(3), Ok(45));
+        assert_eq!(triangular.value_at(100), Ok(5151));
+
+        let cubic = Sequence::<i64>::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(cubic.degree(), 3);
+        assert_eq!(cubic.extrapolate_forward(1), Ok(68));
+        assert_eq!(cubic.extrapolate_backward(1), Ok(5));
+
+        let zeros = Sequence::<i64>::new(&[0, 0, 0]).unwrap();
+        assert_eq!(zeros.degree(), 0);
+        assert_eq!(zeros.value_at(-7), Ok(0));
+    }
+
+    #[test]
+    fn d09p1_overflow() {
+        let history = [i64::MAX - 2, i64::MAX - 1, i64::MAX];
+        let narrow = Sequence::<i64>::new(&history).unwrap();
+        assert_eq!(narrow.extrapolate_forward(1), Err(SequenceError::Overflow));
+        let wide = Sequence::<i128>::new(&history).unwrap();
+        assert_eq!(wide.extrapolate_forward(1), Ok(i64::MAX as i128 + 1));
+
+        assert_eq!(
+            Sequence::<i64>::new(&[i64::MIN, 0, 0, i64::MIN]).unwrap_err(),
+            SequenceError::Overflow
+        );
+        assert!(Sequence::<i128>::new(&[i64::MIN, 0, 0, i64::MIN]).is_ok());
+
+        let cubes = Sequence::<i128>::new(&[0, 1, 8, 27, 64]).unwrap();
+        assert_eq!(cubes.value_at(1_000_000), Ok(1_000_000_000_000_000_000));
+        assert_eq!(
+            cubes.value_at(1_000_000_000_000_000),
+            Err(SequenceError::Overflow)
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn d09p1_bigint() {
+        use num_bigint::BigInt;
+
+        let cubes = Sequence::<BigInt>::new(&[0, 1, 8, 27, 64]).unwrap();
+        assert_eq!(
+            cubes.value_at(1_000_000_000_000_000),
+            Ok(BigInt::from(10).pow(45))
+        );
+    }
+
+    #[test]
+    fn d09p1_not_polynomial() {
+        let powers_of_two = Sequence::<i64>::new(&[1, 2, 4, 8, 16]);
+        assert_eq!(
+            powers_of_two.unwrap_err(),
+            SequenceError::NotPolynomial { length: 5 }
+        );
+        assert!(Sequence::<i64>::new(&[]).is_err());
+        assert!(parse_report("1 2 x").is_err());
+    }
 }
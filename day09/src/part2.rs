@@ -1,10 +1,7 @@
 use std::time::Instant;
 
-use itertools::Itertools;
-
-use crate::parser::parse_report;
-
-mod parser;
+use day09::parser::parse_report;
+use day09::sequence::Sequence;
 
 fn main() {
     let start_time = Instant::now();
@@ -15,30 +12,15 @@ fn main() {
 }
 
 fn part2(input: &str) -> i64 {
-    let report = parse_report(input);
-    report.iter().map(extrapolate_previous_values).sum()
-}
-
-fn extrapolate_previous_values(history: &Vec<i64>) -> i64 {
-    let mut differences = calculate_differences(history);
-    let mut first_elements: Vec<i64> = vec![*differences.first().unwrap()];
-
-    while !differences.iter().all(|&x| x == 0) {
-        differences = calculate_differences(&differences);
-        first_elements.push(*differences.first().unwrap());
-    }
-
-    let result = first_elements.iter().rev().fold(0, |acc, num| num - acc);
-
-    history.first().unwrap() - result
-}
-
-fn calculate_differences(input: &[i64]) -> Vec<i64> {
-    input
+    let report = parse_report(input).expect("should parse report");
+    report
         .iter()
-        .tuple_windows()
-        .map(|(first, second)| second - first)
-        .collect()
+        .map(|history| {
+            Sequence::<i64>::new(history)
+                .and_then(|sequence| sequence.extrapolate_backward(1))
+                .expect("history should be a polynomial sequence that fits in i64")
+        })
+        .sum()
 }
 
 #[cfg(test)]
@@ -0,0 +1,191 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "bigint")]
+use num_bigint::{BigInt, Sign};
+
+/// Integer type the difference table is built and the polynomial is evaluated in.
+///
+/// Every operation is checked, so a backend that is too narrow for a history reports
+/// [`SequenceError::Overflow`] instead of wrapping around.
+pub trait Value: Clone {
+    fn from_i64(value: i64) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+    fn is_zero(&self) -> bool;
+}
+
+macro_rules! impl_value {
+    ($($t:ty),*) => {
+        $(
+            impl Value for $t {
+                fn from_i64(value: i64) -> Self {
+                    value as $t
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+
+                fn checked_div(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_div(*self, *other)
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_value!(i64, i128);
+
+#[cfg(feature = "bigint")]
+impl Value for BigInt {
+    fn from_i64(value: i64) -> Self {
+        BigInt::from(value)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        (other.sign() != Sign::NoSign).then(|| self / other)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.sign() == Sign::NoSign
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SequenceError {
+    /// An intermediate value did not fit in the chosen [`Value`] type.
+    Overflow,
+    /// The difference table ran out of values before reaching a row of zeros, so the history
+    /// does not pin down a polynomial and any extrapolation would be a guess.
+    NotPolynomial { length: usize },
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SequenceError::Overflow => write!(f, "value does not fit in the chosen integer type"),
+            SequenceError::NotPolynomial { length } => write!(
+                f,
+                "differences of the {length} value history never reach a row of zeros"
+            ),
+        }
+    }
+}
+
+impl Error for SequenceError {}
+
+/// A history of readings together with its difference table, computed once.
+///
+/// Only the first element of every row of the difference table is kept. By Newton's forward
+/// difference formula those are enough to evaluate the fitted polynomial at any index:
+/// `value(n) = sum over k of C(n, k) * first_differences[k]`.
+#[derive(Debug)]
+pub struct Sequence<T> {
+    first_differences: Vec<T>,
+    len: usize,
+}
+
+impl<T: Value> Sequence<T> {
+    /// Builds the difference table of `history` until a row of zeros is reached.
+    pub fn new(history: &[i64]) -> Result<Self, SequenceError> {
+        let mut first_differences = Vec::new();
+        let mut differences: Vec<T> = history.iter().map(|&x| T::from_i64(x)).collect();
+
+        loop {
+            match differences.first() {
+                None => {
+                    return Err(SequenceError::NotPolynomial {
+                        length: history.len(),
+                    })
+                }
+                Some(_) if differences.iter().all(T::is_zero) => break,
+                Some(first) => {
+                    first_differences.push(first.clone());
+                    differences = calculate_differences(&differences)?;
+                }
+            }
+        }
+
+        Ok(Sequence {
+            first_differences,
+            len: history.len(),
+        })
+    }
+
+    /// Returns the degree of the fitted polynomial. A sequence of zeros has degree 0.
+    pub fn degree(&self) -> usize {
+        self.first_differences.len().saturating_sub(1)
+    }
+
+    /// Evaluates the fitted polynomial at `index`, where index 0 is the first value of the history.
+    /// Negative indexes lie before the history.
+    pub fn value_at(&self, index: i64) -> Result<T, SequenceError> {
+        let mut binomial = T::from_i64(1);
+        let mut value = T::from_i64(0);
+
+        for (k, difference) in self.first_differences.iter().enumerate() {
+            let term = binomial
+                .checked_mul(difference)
+                .ok_or(SequenceError::Overflow)?;
+            value = value.checked_add(&term).ok_or(SequenceError::Overflow)?;
+
+            // C(n, k + 1) = C(n, k) * (n - k) / (k + 1), and the division is always exact
+            let factor = index.checked_sub(k as i64).ok_or(SequenceError::Overflow)?;
+            binomial = binomial
+                .checked_mul(&T::from_i64(factor))
+                .and_then(|product| product.checked_div(&T::from_i64(k as i64 + 1)))
+                .ok_or(SequenceError::Overflow)?;
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the value `steps` positions after the last value of the history.
+    pub fn extrapolate_forward(&self, steps: usize) -> Result<T, SequenceError> {
+        let index = (self.len - 1)
+            .checked_add(steps)
+            .and_then(|index| i64::try_from(index).ok())
+            .ok_or(SequenceError::Overflow)?;
+        self.value_at(index)
+    }
+
+    /// Returns the value `steps` positions before the first value of the history.
+    pub fn extrapolate_backward(&self, steps: usize) -> Result<T, SequenceError> {
+        let steps = i64::try_from(steps).map_err(|_| SequenceError::Overflow)?;
+        self.value_at(-steps)
+    }
+}
+
+fn calculate_differences<T: Value>(input: &[T]) -> Result<Vec<T>, SequenceError> {
+    input
+        .windows(2)
+        .map(|pair| pair[1].checked_sub(&pair[0]).ok_or(SequenceError::Overflow))
+        .collect()
+}
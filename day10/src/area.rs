@@ -0,0 +1,26 @@
+use glam::IVec2;
+
+/// Counts the tiles enclosed by a loop given its tiles in walking order.
+///
+/// The shoelace formula gives the area of the polygon through the tile centres, and Pick's theorem
+/// (`area = interior + boundary / 2 - 1`) turns that into the number of interior tiles. Only the
+/// positions are used, so the result does not depend on which pipe the starting position hides.
+/// Fewer than 4 tiles can't form a loop and a loop that doubles back on itself encloses nothing,
+/// so both count 0 tiles.
+pub fn enclosed_tile_count(loop_tiles: &[IVec2]) -> u64 {
+    if loop_tiles.len() < 4 {
+        return 0;
+    }
+
+    let doubled_area = loop_tiles
+        .iter()
+        .zip(loop_tiles.iter().cycle().skip(1))
+        .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+        .sum::<i64>()
+        .unsigned_abs();
+    let boundary = loop_tiles.len() as u64;
+
+    (doubled_area + 2)
+        .checked_sub(boundary)
+        .map_or(0, |twice_interior| twice_interior / 2)
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use glam::IVec2;
+
+use crate::pipe_map::PipeMap;
+
+const SCALE: i32 = 3;
+
+/// Returns the tiles enclosed by the main loop, found by flood-filling the outside of the map.
+///
+/// Every tile becomes a 3x3 block in which only the loop's pipes are solid: the centre and the
+/// cells towards the directions the pipe connects. Two pipes that merely touch then always leave a
+/// gap between them, so the fill can squeeze through. The grid also gets a one cell border so the
+/// outside is a single connected region. Tiles whose centre the fill never reaches are enclosed.
+pub fn enclosed_tiles(pipe_map: &PipeMap) -> HashSet<IVec2> {
+    let main_loop = pipe_map.main_loop();
+    let size = pipe_map.size() * SCALE + 2;
+    let index = |pos: IVec2| (pos.y * size.x + pos.x) as usize;
+    let to_fine = |tile: IVec2| tile * SCALE + 1 + SCALE / 2;
+
+    let mut solid = vec![false; (size.x * size.y) as usize];
+    for &tile in &main_loop {
+        let centre = to_fine(tile);
+        solid[index(centre)] = true;
+        for direction in pipe_map
+            .shape_at(tile)
+            .and_then(|pipe_type| pipe_type.connections())
+            .expect("loop tiles should be pipes")
+        {
+            solid[index(centre + direction.offset())] = true;
+        }
+    }
+
+    let mut outside = vec![false; solid.len()];
+    let mut stack = vec![IVec2::ZERO];
+    outside[0] = true;
+    while let Some(pos) = stack.pop() {
+        for offset in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+            let next = pos + offset;
+            if next.cmplt(IVec2::ZERO).any() || next.cmpge(size).any() {
+                continue;
+            }
+            let i = index(next);
+            if !solid[i] && !outside[i] {
+                outside[i] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    let main_loop: HashSet<IVec2> = main_loop.into_iter().collect();
+    let tiles = pipe_map.size();
+    (0..tiles.y)
+        .flat_map(|y| (0..tiles.x).map(move |x| IVec2::new(x, y)))
+        .filter(|tile| !main_loop.contains(tile) && !outside[index(to_fine(*tile))])
+        .collect()
+}
@@ -0,0 +1,5 @@
+pub mod area;
+pub mod flood_fill;
+pub mod parser;
+pub mod pipe_map;
+pub mod render;
@@ -10,7 +10,7 @@ use nom::sequence::terminated;
 use nom::{IResult, Parser};
 use nom_locate::LocatedSpan;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum PipeType {
     NorthSouthVerticalPipe, // |
     EastWestHorizontalPipe, // -
@@ -22,7 +22,7 @@ pub enum PipeType {
     StartingPosition,       // S
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Direction {
     North,
     South,
@@ -30,6 +30,74 @@ pub enum Direction {
     West,
 }
 
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    pub fn offset(&self) -> IVec2 {
+        match self {
+            Direction::North => IVec2::new(0, -1),
+            Direction::South => IVec2::new(0, 1),
+            Direction::East => IVec2::new(1, 0),
+            Direction::West => IVec2::new(-1, 0),
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl PipeType {
+    /// Returns the two directions a pipe connects, in [`Direction::ALL`] order.
+    /// Ground and the starting position connect nothing on their own.
+    pub fn connections(&self) -> Option<[Direction; 2]> {
+        match self {
+            PipeType::NorthSouthVerticalPipe => Some([Direction::North, Direction::South]),
+            PipeType::EastWestHorizontalPipe => Some([Direction::East, Direction::West]),
+            PipeType::NorthEastBend => Some([Direction::North, Direction::East]),
+            PipeType::NorthWestBend => Some([Direction::North, Direction::West]),
+            PipeType::SouthWestBend => Some([Direction::South, Direction::West]),
+            PipeType::SouthEastBend => Some([Direction::South, Direction::East]),
+            PipeType::Ground | PipeType::StartingPosition => None,
+        }
+    }
+
+    /// Returns the pipe connecting `a` and `b`, or `None` if they are the same direction.
+    pub fn from_connections(a: Direction, b: Direction) -> Option<PipeType> {
+        if a == b {
+            return None;
+        }
+        PipeType::ALL_PIPES
+            .into_iter()
+            .find(|pipe_type| pipe_type.connects(a) && pipe_type.connects(b))
+    }
+
+    /// Returns true if the pipe has an opening towards `direction`.
+    pub fn connects(&self, direction: Direction) -> bool {
+        self.connections()
+            .is_some_and(|connections| connections.contains(&direction))
+    }
+
+    const ALL_PIPES: [PipeType; 6] = [
+        PipeType::NorthSouthVerticalPipe,
+        PipeType::EastWestHorizontalPipe,
+        PipeType::NorthEastBend,
+        PipeType::NorthWestBend,
+        PipeType::SouthWestBend,
+        PipeType::SouthEastBend,
+    ];
+}
+
 #[derive(Debug)]
 pub struct PipeInfo<'a> {
     pub span: LocatedSpan<&'a str, IVec2>,
@@ -1,13 +1,9 @@
-use std::iter::successors;
 use std::time::Instant;
 
-use glam::IVec2;
+use day10::parser::parse_input;
+use day10::pipe_map::PipeMap;
 use nom_locate::LocatedSpan;
 
-use crate::parser::{parse_input, Direction, PipeType};
-
-mod parser;
-
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p1.txt");
@@ -18,112 +14,17 @@ fn main() {
 
 fn part1(input: &str) -> u32 {
     let (_input, pipes) = parse_input(LocatedSpan::new(input), false).expect("should parse input");
+    let pipe_map = PipeMap::new(pipes);
 
-    let start_pos = pipes
-        .iter()
-        .find_map(|(k, v)| (v == &PipeType::StartingPosition).then_some(k))
-        .expect("should find starting position");
-
-    let north = *start_pos + IVec2::new(0, -1);
-    let north_pos = pipes
-        .get(&north)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::NorthSouthVerticalPipe
-                    | PipeType::SouthEastBend
-                    | PipeType::SouthWestBend
-            )
-        })
-        .then_some((Direction::South, north));
-
-    let south = *start_pos + IVec2::new(0, 1);
-    let south_pos = pipes
-        .get(&south)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::NorthSouthVerticalPipe
-                    | PipeType::NorthEastBend
-                    | PipeType::NorthWestBend
-            )
-        })
-        .then_some((Direction::North, south));
-
-    let east = *start_pos + IVec2::new(1, 0);
-    let east_pos = pipes
-        .get(&east)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::EastWestHorizontalPipe
-                    | PipeType::NorthWestBend
-                    | PipeType::SouthWestBend
-            )
-        })
-        .then_some((Direction::West, east));
-
-    let west = *start_pos + IVec2::new(-1, 0);
-    let west_pos = pipes
-        .get(&west)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::EastWestHorizontalPipe
-                    | PipeType::NorthEastBend
-                    | PipeType::SouthEastBend
-            )
-        })
-        .then_some((Direction::East, west));
-
-    let mut iters = vec![north_pos, south_pos, east_pos, west_pos]
-        .into_iter()
-        .flatten()
-        .map(|tuple| {
-            successors(Some(tuple), |(coming_from_direction, current_pos)| {
-                let pipe_type = pipes
-                    .get(current_pos)
-                    .expect("should not ask for non-existent position");
-
-                let next_direction = match (coming_from_direction, pipe_type) {
-                    (Direction::North, PipeType::NorthSouthVerticalPipe) => Direction::South,
-                    (Direction::North, PipeType::NorthEastBend) => Direction::East,
-                    (Direction::North, PipeType::NorthWestBend) => Direction::West,
-                    (Direction::South, PipeType::NorthSouthVerticalPipe) => Direction::North,
-                    (Direction::South, PipeType::SouthEastBend) => Direction::East,
-                    (Direction::South, PipeType::SouthWestBend) => Direction::West,
-                    (Direction::East, PipeType::EastWestHorizontalPipe) => Direction::West,
-                    (Direction::East, PipeType::NorthEastBend) => Direction::North,
-                    (Direction::East, PipeType::SouthEastBend) => Direction::South,
-                    (Direction::West, PipeType::EastWestHorizontalPipe) => Direction::East,
-                    (Direction::West, PipeType::NorthWestBend) => Direction::North,
-                    (Direction::West, PipeType::SouthWestBend) => Direction::South,
-                    _ => panic!("unable to get next direction"),
-                };
-
-                Some(match next_direction {
-                    Direction::North => (Direction::South, *current_pos + IVec2::new(0, -1)),
-                    Direction::South => (Direction::North, *current_pos + IVec2::new(0, 1)),
-                    Direction::East => (Direction::West, *current_pos + IVec2::new(1, 0)),
-                    Direction::West => (Direction::East, *current_pos + IVec2::new(-1, 0)),
-                })
-            })
-        });
-
-    let path_a = iters.next().expect("path A should exist");
-    let path_b = iters.next().expect("path B should exist");
-
-    let final_pos = path_a
-        .zip(path_b)
-        .position(|(a, b)| a.1 == b.1)
-        .expect("should meet in the middle");
-
-    final_pos as u32 + 1
+    // The farthest tile is halfway around the loop
+    pipe_map.main_loop().len() as u32 / 2
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day10::parser::PipeType;
+    use glam::IVec2;
 
     #[test]
     fn d10p1_input1() {
@@ -146,4 +47,52 @@ LJ...";
 
         assert_eq!(part1(input), 8);
     }
+
+    #[test]
+    fn d10p1_pipe_map() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+        let (_input, pipes) =
+            parse_input(LocatedSpan::new(input), false).expect("should parse input");
+        let pipe_map = PipeMap::new(pipes);
+        assert_eq!(pipe_map.start(), IVec2::new(1, 1));
+        assert_eq!(pipe_map.start_shape(), PipeType::SouthEastBend);
+        assert_eq!(
+            pipe_map.shape_at(IVec2::new(1, 1)),
+            Some(PipeType::SouthEastBend)
+        );
+        assert_eq!(
+            pipe_map.main_loop(),
+            vec![
+                IVec2::new(1, 1),
+                IVec2::new(1, 2),
+                IVec2::new(1, 3),
+                IVec2::new(2, 3),
+                IVec2::new(3, 3),
+                IVec2::new(3, 2),
+                IVec2::new(3, 1),
+                IVec2::new(2, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn d10p1_start_with_extra_neighbours() {
+        // All four neighbours point at S, but only the south and east ones lead back to it
+        let input = ".|...
+-S-7.
+.|.|.
+.L-J.";
+
+        let (_input, pipes) =
+            parse_input(LocatedSpan::new(input), false).expect("should parse input");
+        let pipe_map = PipeMap::new(pipes);
+        assert_eq!(pipe_map.start_shape(), PipeType::SouthEastBend);
+        assert_eq!(pipe_map.main_loop().len(), 8);
+        assert_eq!(part1(input), 4);
+    }
 }
@@ -1,14 +1,15 @@
 use std::collections::HashSet;
-use std::iter::successors;
+use std::env;
 use std::time::Instant;
 
+use day10::area::enclosed_tile_count;
+use day10::flood_fill;
+use day10::parser::{parse_input, PipeType};
+use day10::pipe_map::PipeMap;
+use day10::render::{to_svg, to_terminal};
 use glam::IVec2;
 use nom_locate::LocatedSpan;
 
-use crate::parser::{parse_input, Direction, PipeType};
-
-mod parser;
-
 #[derive(Debug, Eq, PartialEq)]
 enum Status {
     In,
@@ -18,7 +19,21 @@ enum Status {
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p2.txt");
-    let solution = part2(input_file);
+    if env::args().any(|arg| arg == "--render") {
+        print!("{}", render(input_file, false));
+        return;
+    }
+    if env::args().any(|arg| arg == "--svg") {
+        print!("{}", render(input_file, true));
+        return;
+    }
+    let solution = if env::args().any(|arg| arg == "--shoelace") {
+        part2_shoelace(input_file)
+    } else if env::args().any(|arg| arg == "--flood-fill") {
+        part2_flood_fill(input_file)
+    } else {
+        part2(input_file)
+    };
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
@@ -26,148 +41,75 @@ fn main() {
 fn part2(input: &str) -> u32 {
     let (_input, pipes) = parse_input(LocatedSpan::new(input), true).expect("should parse input");
 
-    let start_pos = pipes
-        .iter()
-        .find_map(|(k, v)| (v == &PipeType::StartingPosition).then_some(k))
-        .expect("should find starting position");
-
-    let north = *start_pos + IVec2::new(0, -1);
-    let north_pos = pipes
-        .get(&north)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::NorthSouthVerticalPipe
-                    | PipeType::SouthEastBend
-                    | PipeType::SouthWestBend
-            )
-        })
-        .then_some((Direction::South, north));
-
-    let south = *start_pos + IVec2::new(0, 1);
-    let south_pos = pipes
-        .get(&south)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::NorthSouthVerticalPipe
-                    | PipeType::NorthEastBend
-                    | PipeType::NorthWestBend
-            )
-        })
-        .then_some((Direction::North, south));
-
-    let east = *start_pos + IVec2::new(1, 0);
-    let east_pos = pipes
-        .get(&east)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::EastWestHorizontalPipe
-                    | PipeType::NorthWestBend
-                    | PipeType::SouthWestBend
-            )
-        })
-        .then_some((Direction::West, east));
-
-    let west = *start_pos + IVec2::new(-1, 0);
-    let west_pos = pipes
-        .get(&west)
-        .is_some_and(|pipe_type| {
-            matches!(
-                pipe_type,
-                PipeType::EastWestHorizontalPipe
-                    | PipeType::NorthEastBend
-                    | PipeType::SouthEastBend
-            )
-        })
-        .then_some((Direction::East, west));
-
-    let mut iters = vec![north_pos, south_pos, east_pos, west_pos]
-        .into_iter()
-        .flatten()
-        .map(|tuple| {
-            successors(Some(tuple), |(coming_from_direction, current_pos)| {
-                let pipe_type = pipes
-                    .get(current_pos)
-                    .expect("should not ask for non-existent position");
-
-                let next_direction = match (coming_from_direction, pipe_type) {
-                    (Direction::North, PipeType::NorthSouthVerticalPipe) => Direction::South,
-                    (Direction::North, PipeType::NorthEastBend) => Direction::East,
-                    (Direction::North, PipeType::NorthWestBend) => Direction::West,
-                    (Direction::South, PipeType::NorthSouthVerticalPipe) => Direction::North,
-                    (Direction::South, PipeType::SouthEastBend) => Direction::East,
-                    (Direction::South, PipeType::SouthWestBend) => Direction::West,
-                    (Direction::East, PipeType::EastWestHorizontalPipe) => Direction::West,
-                    (Direction::East, PipeType::NorthEastBend) => Direction::North,
-                    (Direction::East, PipeType::SouthEastBend) => Direction::South,
-                    (Direction::West, PipeType::EastWestHorizontalPipe) => Direction::East,
-                    (Direction::West, PipeType::NorthWestBend) => Direction::North,
-                    (Direction::West, PipeType::SouthWestBend) => Direction::South,
-                    _ => panic!("unable to get next direction"),
-                };
-
-                Some(match next_direction {
-                    Direction::North => (Direction::South, *current_pos + IVec2::new(0, -1)),
-                    Direction::South => (Direction::North, *current_pos + IVec2::new(0, 1)),
-                    Direction::East => (Direction::West, *current_pos + IVec2::new(1, 0)),
-                    Direction::West => (Direction::East, *current_pos + IVec2::new(-1, 0)),
-                })
-            })
-        });
-
-    let path_a = iters.next().expect("path A should exist");
-    let path_b = iters.next().expect("path B should exist");
-    let zipped = path_a.zip(path_b);
-    let mut pipe_locations: HashSet<IVec2> = HashSet::from([*start_pos]);
-
-    for (path_a_node, path_b_node) in zipped {
-        pipe_locations.insert(path_a_node.1);
-        pipe_locations.insert(path_b_node.1);
+    let pipe_map = PipeMap::new(pipes);
+    enclosed_tiles(input, &pipe_map).len() as u32
+}
 
-        if path_a_node.1 == path_b_node.1 {
-            break;
-        }
-    }
+/// Scans every row, toggling between outside and inside whenever it crosses a loop pipe with an
+/// opening to the south.
+fn enclosed_tiles(input: &str, pipe_map: &PipeMap) -> HashSet<IVec2> {
+    let pipe_locations: HashSet<IVec2> = pipe_map.main_loop().into_iter().collect();
 
     input
         .lines()
         .enumerate()
-        .map(|(y, line)| {
+        .flat_map(|(y, line)| {
             let mut status = Status::Out;
+            let pipe_locations = &pipe_locations;
+
+            line.chars().enumerate().filter_map(move |(x, _)| {
+                let pos = IVec2::new(x as i32, y as i32);
+                let pipe_type = pipe_map.shape_at(pos).expect("should get a valid pipe");
 
-            line.chars()
-                .enumerate()
-                .filter(|(x, _)| {
-                    let pos = IVec2::new(*x as i32, y as i32);
-                    let pipe_type = pipes.get(&pos).expect("should get a valid pipe");
-
-                    if pipe_locations.contains(&pos) {
-                        if [
-                            PipeType::StartingPosition,
-                            PipeType::NorthSouthVerticalPipe,
-                            PipeType::SouthWestBend,
-                            PipeType::SouthEastBend,
-                        ]
-                        .contains(pipe_type)
-                        {
-                            status = match status {
-                                Status::In => Status::Out,
-                                Status::Out => Status::In,
-                            };
+                if pipe_locations.contains(&pos) {
+                    if [
+                        PipeType::NorthSouthVerticalPipe,
+                        PipeType::SouthWestBend,
+                        PipeType::SouthEastBend,
+                    ]
+                    .contains(&pipe_type)
+                    {
+                        status = match status {
+                            Status::In => Status::Out,
+                            Status::Out => Status::In,
                         };
-                        false
-                    } else {
-                        match status {
-                            Status::In => true,
-                            Status::Out => false,
-                        }
+                    };
+                    None
+                } else {
+                    match status {
+                        Status::In => Some(pos),
+                        Status::Out => None,
                     }
-                })
-                .count() as u32
+                }
+            })
         })
-        .sum::<u32>()
+        .collect()
+}
+
+fn part2_shoelace(input: &str) -> u32 {
+    let (_input, pipes) = parse_input(LocatedSpan::new(input), true).expect("should parse input");
+
+    let pipe_map = PipeMap::new(pipes);
+    enclosed_tile_count(&pipe_map.main_loop()) as u32
+}
+
+fn part2_flood_fill(input: &str) -> u32 {
+    let (_input, pipes) = parse_input(LocatedSpan::new(input), true).expect("should parse input");
+
+    let pipe_map = PipeMap::new(pipes);
+    flood_fill::enclosed_tiles(&pipe_map).len() as u32
+}
+
+fn render(input: &str, svg: bool) -> String {
+    let (_input, pipes) = parse_input(LocatedSpan::new(input), true).expect("should parse input");
+
+    let pipe_map = PipeMap::new(pipes);
+    let enclosed = enclosed_tiles(input, &pipe_map);
+    if svg {
+        to_svg(&pipe_map, &enclosed)
+    } else {
+        to_terminal(&pipe_map, &enclosed)
+    }
 }
 
 #[cfg(test)]
@@ -187,6 +129,8 @@ mod tests {
 ...........";
 
         assert_eq!(part2(input), 4);
+        assert_eq!(part2_shoelace(input), 4);
+        assert_eq!(part2_flood_fill(input), 4);
     }
 
     #[test]
@@ -203,6 +147,8 @@ L--J.L7...LJS7F-7L7.
 ....L---J.LJ.LJLJ...";
 
         assert_eq!(part2(input), 8);
+        assert_eq!(part2_shoelace(input), 8);
+        assert_eq!(part2_flood_fill(input), 8);
     }
 
     #[test]
@@ -219,5 +165,98 @@ L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
 
         assert_eq!(part2(input), 10);
+        assert_eq!(part2_shoelace(input), 10);
+        assert_eq!(part2_flood_fill(input), 10);
+    }
+
+    #[test]
+    fn d10p2_hidden_start_shape() {
+        // S hides a J here, so a scan-line that treats S like | would count the tile after it
+        let input = ".....
+.F-7.
+.|.|.
+.L-S.
+.....";
+
+        assert_eq!(part2(input), 1);
+        assert_eq!(part2_shoelace(input), 1);
+        assert_eq!(part2_flood_fill(input), 1);
+
+        // S hides a -
+        let input = ".....
+.FS7.
+.|.|.
+.L-J.
+.....";
+
+        assert_eq!(part2(input), 1);
+        assert_eq!(part2_shoelace(input), 1);
+        assert_eq!(part2_flood_fill(input), 1);
+    }
+
+    #[test]
+    fn d10p2_degenerate_loops() {
+        assert_eq!(enclosed_tile_count(&[]), 0);
+        assert_eq!(enclosed_tile_count(&[IVec2::new(1, 1)]), 0);
+
+        // A walk there and back along one row has no area
+        let there_and_back = [0, 1, 2, 3, 2, 1].map(|x| IVec2::new(x, 0));
+        assert_eq!(enclosed_tile_count(&there_and_back), 0);
+
+        let square = [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(x, y)| IVec2::new(x, y));
+        assert_eq!(enclosed_tile_count(&square), 0);
+    }
+
+    #[test]
+    fn d10p2_flood_fill_matches_scan_line() {
+        // The ground tiles in the inner ring are outside the loop, but the fill can only reach
+        // them by squeezing between the touching 7F and || pipes below
+        let input = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+        let (_input, pipes) =
+            parse_input(LocatedSpan::new(input), true).expect("should parse input");
+        let pipe_map = PipeMap::new(pipes);
+        let enclosed = flood_fill::enclosed_tiles(&pipe_map);
+        assert_eq!(enclosed, enclosed_tiles(input, &pipe_map));
+        assert_eq!(
+            enclosed,
+            HashSet::from([
+                IVec2::new(2, 6),
+                IVec2::new(3, 6),
+                IVec2::new(6, 6),
+                IVec2::new(7, 6),
+            ])
+        );
+    }
+
+    #[test]
+    fn d10p2_render() {
+        let input = "......
+.S-7F.
+.|.||.
+.L-JL.
+......";
+
+        let terminal = render(input, false);
+        assert_eq!(terminal.lines().count(), 5);
+        assert!(terminal.contains("\x1b[1;33m┌\x1b[0m\x1b[1;33m─\x1b[0m\x1b[1;33m┐\x1b[0m"));
+        assert!(terminal.contains("\x1b[2m┌\x1b[0m"));
+        assert!(terminal.contains("\x1b[1;32m●\x1b[0m"));
+
+        let svg = render(input, true);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"viewBox="0 0 72 60""#));
+        assert_eq!(svg.matches(r##"fill="#7c7""##).count(), 1);
+        assert_eq!(svg.matches(r##"stroke="#d33""##).count(), 16);
+        assert_eq!(svg.matches(r##"stroke="#bbb""##).count(), 6);
+        assert!(svg.trim_end().ends_with("</svg>"));
     }
 }
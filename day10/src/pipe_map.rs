@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use glam::IVec2;
+
+use crate::parser::{Direction, PipeType};
+
+/// Parsed pipe maze with the shape hidden under the starting position already worked out.
+#[derive(Debug)]
+pub struct PipeMap {
+    pipes: HashMap<IVec2, PipeType>,
+    start: IVec2,
+    start_shape: PipeType,
+}
+
+impl PipeMap {
+    /// Finds the starting position and infers its shape from the neighbours that connect back to it.
+    /// If more than two neighbours do, the shape is the one whose loop leads back to the start.
+    pub fn new(pipes: HashMap<IVec2, PipeType>) -> Self {
+        let start = pipes
+            .iter()
+            .find_map(|(pos, pipe_type)| (pipe_type == &PipeType::StartingPosition).then_some(*pos))
+            .expect("should find starting position");
+
+        let connected: Vec<Direction> = Direction::ALL
+            .into_iter()
+            .filter(|direction| {
+                pipes
+                    .get(&(start + direction.offset()))
+                    .is_some_and(|pipe_type| pipe_type.connects(direction.opposite()))
+            })
+            .collect();
+
+        // Leaving through one side, the walk has to come back through the other one
+        let start_shape = connected
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &first)| {
+                connected[index + 1..]
+                    .iter()
+                    .map(move |&second| (first, second))
+            })
+            .find(|&(first, second)| {
+                walk(&pipes, start, first).is_some_and(|(_, arrival)| arrival == second.opposite())
+            })
+            .and_then(|(first, second)| PipeType::from_connections(first, second))
+            .expect("start should be part of a loop");
+
+        PipeMap {
+            pipes,
+            start,
+            start_shape,
+        }
+    }
+
+    pub fn start(&self) -> IVec2 {
+        self.start
+    }
+
+    pub fn start_shape(&self) -> PipeType {
+        self.start_shape
+    }
+
+    /// Returns the width and height of the map.
+    pub fn size(&self) -> IVec2 {
+        self.pipes
+            .keys()
+            .fold(IVec2::ZERO, |size, pos| size.max(*pos + IVec2::ONE))
+    }
+
+    /// Returns the pipe at `pos`, with the starting position replaced by its inferred shape.
+    pub fn shape_at(&self, pos: IVec2) -> Option<PipeType> {
+        match self.pipes.get(&pos) {
+            Some(PipeType::StartingPosition) => Some(self.start_shape),
+            pipe_type => pipe_type.copied(),
+        }
+    }
+
+    /// Returns the tiles of the loop through the starting position, in walking order and starting
+    /// with the starting position itself.
+    pub fn main_loop(&self) -> Vec<IVec2> {
+        let [heading, _] = self
+            .start_shape
+            .connections()
+            .expect("start shape should be a pipe");
+
+        let (tiles, _) =
+            walk(&self.pipes, self.start, heading).expect("loop should not lead off a pipe");
+        tiles
+    }
+}
+
+/// Follows the pipes from `start` in the direction of `heading` until the walk is back at the
+/// start. Returns the visited tiles, starting with `start`, and the heading the start was
+/// reached with, or `None` if the walk leads off a pipe.
+fn walk(
+    pipes: &HashMap<IVec2, PipeType>,
+    start: IVec2,
+    mut heading: Direction,
+) -> Option<(Vec<IVec2>, Direction)> {
+    let mut current = start;
+    let mut tiles = vec![current];
+
+    loop {
+        current += heading.offset();
+        if current == start {
+            return Some((tiles, heading));
+        }
+        tiles.push(current);
+
+        let came_from = heading.opposite();
+        heading = pipes
+            .get(&current)
+            .and_then(|pipe_type| pipe_type.connections())
+            .filter(|connections| connections.contains(&came_from))
+            .and_then(|connections| connections.into_iter().find(|&d| d != came_from))?;
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use glam::IVec2;
+
+use crate::parser::PipeType;
+use crate::pipe_map::PipeMap;
+
+const LOOP_STYLE: &str = "\x1b[1;33m";
+const DIM_STYLE: &str = "\x1b[2m";
+const ENCLOSED_STYLE: &str = "\x1b[1;32m";
+const RESET_STYLE: &str = "\x1b[0m";
+
+const TILE_SIZE: i32 = 12;
+
+/// Renders the maze with box-drawing characters and ANSI colours. Loop pipes are bold yellow,
+/// pipes off the loop are dimmed and enclosed tiles are shown as green dots.
+pub fn to_terminal(pipe_map: &PipeMap, enclosed: &HashSet<IVec2>) -> String {
+    let main_loop: HashSet<IVec2> = pipe_map.main_loop().into_iter().collect();
+    let size = pipe_map.size();
+    let mut output = String::new();
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let pos = IVec2::new(x, y);
+            let pipe_type = pipe_map.shape_at(pos).unwrap_or(PipeType::Ground);
+
+            if main_loop.contains(&pos) {
+                output.push_str(LOOP_STYLE);
+                output.push(box_drawing(pipe_type));
+                output.push_str(RESET_STYLE);
+            } else if enclosed.contains(&pos) {
+                output.push_str(ENCLOSED_STYLE);
+                output.push('●');
+                output.push_str(RESET_STYLE);
+            } else if pipe_type == PipeType::Ground {
+                output.push(' ');
+            } else {
+                output.push_str(DIM_STYLE);
+                output.push(box_drawing(pipe_type));
+                output.push_str(RESET_STYLE);
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Renders the maze as an SVG image, with the same highlighting as [`to_terminal`]. Every pipe is
+/// drawn as lines from the centre of its tile to the edges it connects.
+pub fn to_svg(pipe_map: &PipeMap, enclosed: &HashSet<IVec2>) -> String {
+    let main_loop: HashSet<IVec2> = pipe_map.main_loop().into_iter().collect();
+    let size = pipe_map.size();
+    let mut svg = String::new();
+
+    let view_box = size * TILE_SIZE;
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}">"#,
+        view_box.x, view_box.y
+    )
+    .expect("should write to string");
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#)
+        .expect("should write to string");
+
+    let mut positions: Vec<&IVec2> = enclosed.iter().collect();
+    positions.sort_by_key(|pos| (pos.y, pos.x));
+    for pos in positions {
+        let corner = *pos * TILE_SIZE;
+        writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{TILE_SIZE}" height="{TILE_SIZE}" fill="#7c7"/>"##,
+            corner.x, corner.y
+        )
+        .expect("should write to string");
+    }
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let pos = IVec2::new(x, y);
+            let Some(connections) = pipe_map.shape_at(pos).and_then(|p| p.connections()) else {
+                continue;
+            };
+            let (stroke, width) = if main_loop.contains(&pos) {
+                ("#d33", 3)
+            } else {
+                ("#bbb", 1)
+            };
+
+            let centre = pos * TILE_SIZE + TILE_SIZE / 2;
+            for direction in connections {
+                let edge = centre + direction.offset() * TILE_SIZE / 2;
+                writeln!(
+                    svg,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{stroke}" stroke-width="{width}" stroke-linecap="round"/>"#,
+                    centre.x, centre.y, edge.x, edge.y
+                )
+                .expect("should write to string");
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn box_drawing(pipe_type: PipeType) -> char {
+    match pipe_type {
+        PipeType::NorthSouthVerticalPipe => '│',
+        PipeType::EastWestHorizontalPipe => '─',
+        PipeType::NorthEastBend => '└',
+        PipeType::NorthWestBend => '┘',
+        PipeType::SouthWestBend => '┐',
+        PipeType::SouthEastBend => '┌',
+        PipeType::Ground => ' ',
+        PipeType::StartingPosition => 'S',
+    }
+}
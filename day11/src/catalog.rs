@@ -0,0 +1,188 @@
+use std::fmt;
+use std::fmt::Write;
+
+use glam::I64Vec2;
+
+use crate::universe::galaxy_manhattan_distance;
+
+/// Two galaxies, by their 1-based numbers, and the distance between them.
+#[derive(Debug, Eq, PartialEq)]
+pub struct GalaxyPair {
+    pub first: usize,
+    pub second: usize,
+    pub distance: i64,
+}
+
+impl fmt::Display for GalaxyPair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "galaxies {} and {} ({} apart)",
+            self.first, self.second, self.distance
+        )
+    }
+}
+
+/// Galaxy coordinates numbered from 1 in reading order, the way the puzzle text refers to them.
+#[derive(Debug)]
+pub struct GalaxyCatalog {
+    galaxies: Vec<I64Vec2>,
+}
+
+impl GalaxyCatalog {
+    /// Creates a catalog from galaxy coordinates in reading order.
+    ///
+    /// # Arguments
+    ///
+    /// * `galaxies` - The coordinates, usually from `Universe::expanded_galaxies`.
+    pub fn new(galaxies: Vec<I64Vec2>) -> Self {
+        GalaxyCatalog { galaxies }
+    }
+
+    pub fn len(&self) -> usize {
+        self.galaxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.galaxies.is_empty()
+    }
+
+    /// Returns the coordinates of the galaxy with the given 1-based number.
+    pub fn galaxy(&self, number: usize) -> Option<I64Vec2> {
+        number
+            .checked_sub(1)
+            .and_then(|idx| self.galaxies.get(idx))
+            .copied()
+    }
+
+    /// Returns the length of the shortest path between two galaxies, given by their 1-based numbers.
+    pub fn distance(&self, first: usize, second: usize) -> Option<i64> {
+        Some(galaxy_manhattan_distance(
+            &self.galaxy(first)?,
+            &self.galaxy(second)?,
+        ))
+    }
+
+    /// Walks one of the shortest paths between two galaxies, first along the row and then along the
+    /// column.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The 1-based number of the galaxy to start at.
+    /// * `second` - The 1-based number of the galaxy to end at.
+    ///
+    /// # Returns
+    ///
+    /// A lazy iterator over every tile of the path, including both galaxies, so it yields
+    /// `distance + 1` tiles. `None` if either galaxy does not exist.
+    pub fn shortest_path(
+        &self,
+        first: usize,
+        second: usize,
+    ) -> Option<impl Iterator<Item = I64Vec2>> {
+        let from = self.galaxy(first)?;
+        let to = self.galaxy(second)?;
+        let step = (to - from).signum();
+
+        let horizontal =
+            (0..(to.x - from.x).abs()).map(move |i| from + I64Vec2::new(step.x * i, 0));
+        let vertical =
+            (0..=(to.y - from.y).abs()).map(move |i| I64Vec2::new(to.x, from.y + step.y * i));
+        Some(horizontal.chain(vertical))
+    }
+
+    /// Exports the distances between every pair of galaxies as CSV. The first row and column hold the
+    /// galaxy numbers.
+    pub fn distance_matrix_csv(&self) -> String {
+        let mut csv = String::new();
+
+        for number in 1..=self.len() {
+            write!(csv, ",{number}").expect("should write to string");
+        }
+        csv.push('\n');
+
+        for (idx, g1) in self.galaxies.iter().enumerate() {
+            write!(csv, "{}", idx + 1).expect("should write to string");
+            for g2 in &self.galaxies {
+                write!(csv, ",{}", galaxy_manhattan_distance(g1, g2))
+                    .expect("should write to string");
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Finds the closest pair of galaxies, preferring the lowest numbers on ties.
+    ///
+    /// Galaxies are swept in order of their x coordinate, and each one is only compared with the
+    /// earlier ones that are not already further away than the best pair along x alone.
+    pub fn nearest_pair(&self) -> Option<GalaxyPair> {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|&idx| self.galaxies[idx].x);
+
+        let mut best: Option<GalaxyPair> = None;
+        for (sorted_idx, &idx) in order.iter().enumerate() {
+            let galaxy = self.galaxies[idx];
+            for &other_idx in order[..sorted_idx].iter().rev() {
+                let other = self.galaxies[other_idx];
+                if best
+                    .as_ref()
+                    .is_some_and(|best| galaxy.x - other.x > best.distance)
+                {
+                    break;
+                }
+
+                let pair = GalaxyPair {
+                    first: idx.min(other_idx) + 1,
+                    second: idx.max(other_idx) + 1,
+                    distance: galaxy_manhattan_distance(&galaxy, &other),
+                };
+                if best.as_ref().is_none_or(|best| {
+                    (pair.distance, pair.first, pair.second)
+                        < (best.distance, best.first, best.second)
+                }) {
+                    best = Some(pair);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Finds one of the pairs of galaxies that are furthest apart.
+    ///
+    /// The Manhattan distance is the larger of `|Δ(x + y)|` and `|Δ(x - y)|`, so the furthest pair is
+    /// made of the extremes of one of those two sums, which takes a single pass to find.
+    pub fn farthest_pair(&self) -> Option<GalaxyPair> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        [|g: &I64Vec2| g.x + g.y, |g: &I64Vec2| g.x - g.y]
+            .into_iter()
+            .map(|key| {
+                let (min_idx, _) = self
+                    .galaxies
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, galaxy)| key(galaxy))
+                    .expect("catalog should not be empty");
+                let (max_idx, _) = self
+                    .galaxies
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, galaxy)| key(galaxy))
+                    .expect("catalog should not be empty");
+                GalaxyPair {
+                    first: min_idx.min(max_idx) + 1,
+                    second: min_idx.max(max_idx) + 1,
+                    distance: galaxy_manhattan_distance(
+                        &self.galaxies[min_idx],
+                        &self.galaxies[max_idx],
+                    ),
+                }
+            })
+            .max_by_key(|pair| pair.distance)
+    }
+}
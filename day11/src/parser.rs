@@ -18,7 +18,7 @@ pub enum DataType {
 /// # Example
 ///
 /// ```
-/// use crate::DataType;
+/// use day11::parser::{parse_input, DataType};
 ///
 /// let input = ".#.\n.#.\n";
 /// let parsed_data = parse_input(input);
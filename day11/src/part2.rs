@@ -1,132 +1,54 @@
-use std::collections::BTreeSet;
+use std::env;
 use std::time::Instant;
 
-use glam::I64Vec2;
-use itertools::Itertools;
-
-use crate::parser::{parse_input, DataType};
-
-mod parser;
+use day11::catalog::GalaxyCatalog;
+use day11::parser::parse_input;
+use day11::universe::{sum_of_pairwise_distances, Universe};
 
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p1.txt");
+    if env::args().any(|arg| arg == "--csv") {
+        print!("{}", catalog(input_file, 1_000_000).distance_matrix_csv());
+        return;
+    }
+    if env::args().any(|arg| arg == "--stats") {
+        print_stats(&catalog(input_file, 1_000_000));
+    }
     let solution = part2(input_file, 1_000_000);
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
 
 fn part2(input: &str, expansion_size: u64) -> i64 {
-    let galaxy_map = parse_input(input);
-    let galaxy_coordinates = get_galaxies_coordinates(&galaxy_map);
-
-    let row_indexes_to_expand = galaxy_map
-        .iter()
-        .enumerate()
-        .filter_map(|(row_idx, vec_of_rows)| {
-            vec_of_rows
-                .iter()
-                .all(|data_type| *data_type == DataType::EmptySpace)
-                .then_some(row_idx)
-        })
-        .collect::<BTreeSet<usize>>();
-
-    let initial_row_len = galaxy_map
-        .first()
-        .map(|v| v.len())
-        .expect("should get value of line_len");
-
-    let mut column_indexes_to_expand: BTreeSet<usize> = BTreeSet::new();
-    for col_idx in 0..initial_row_len {
-        if galaxy_map.iter().all(|vec_of_rows| {
-            vec_of_rows
-                .get(col_idx)
-                .expect("should get element from vector")
-                == &DataType::EmptySpace
-        }) {
-            column_indexes_to_expand.insert(col_idx);
-        }
-    }
+    let universe = Universe::new(&parse_input(input));
 
-    let expanded_galaxy_coordinates = galaxy_coordinates
-        .iter()
-        .map(|galaxy_coord| {
-            let rows_added = row_indexes_to_expand
-                .iter()
-                .filter(|row| row < &&(galaxy_coord.y as usize))
-                .count() as i64
-                * (expansion_size as i64 - 1);
-            let cols_added = column_indexes_to_expand
-                .iter()
-                .filter(|col| col < &&(galaxy_coord.x as usize))
-                .count() as i64
-                * (expansion_size as i64 - 1);
-
-            I64Vec2::new(galaxy_coord.x + cols_added, galaxy_coord.y + rows_added)
-        })
-        .collect::<Vec<I64Vec2>>();
-
-    expanded_galaxy_coordinates
-        .iter()
-        .combinations(2)
-        .map(|combinations_vec| galaxy_manhattan_distance(combinations_vec[0], combinations_vec[1]))
-        .sum()
+    let distances = sum_of_pairwise_distances(&universe.expanded_galaxies(expansion_size as i64));
+    i64::try_from(distances).expect("sum of distances should fit in i64")
 }
 
-/// Retrieves the coordinates of all galaxies in the provided expanded galaxy map.
-///
-/// # Arguments
-///
-/// * `expanded_galaxy_map` - A 2-dimensional vector representing the expanded galaxy map,
-///                           where each position contains a DataType enum value.
-///
-/// # Returns
-///
-/// A vector of I64Vec2 objects, each representing the coordinates of a galaxy.
-fn get_galaxies_coordinates(expanded_galaxy_map: &Vec<Vec<DataType>>) -> Vec<I64Vec2> {
-    let mut galaxies_coordinates: Vec<I64Vec2> = Vec::new();
-
-    expanded_galaxy_map
-        .iter()
-        .enumerate()
-        .for_each(|(row_idx, line)| {
-            line.iter().enumerate().for_each(|(col_idx, data_type)| {
-                if *data_type == DataType::Galaxy {
-                    galaxies_coordinates.push(I64Vec2::new(col_idx as i64, row_idx as i64));
-                }
-            })
-        });
-
-    galaxies_coordinates
+fn catalog(input: &str, expansion_size: u64) -> GalaxyCatalog {
+    let universe = Universe::new(&parse_input(input));
+    GalaxyCatalog::new(universe.expanded_galaxies(expansion_size as i64))
 }
 
-/// Calculates the Manhattan distance between two galaxies.
-///
-/// # Arguments
-///
-/// * `g1` - The first galaxy.
-/// * `g2` - The second galaxy.
-///
-/// # Returns
-///
-/// The Manhattan distance between the two galaxies.
-///
-/// # Example
-///
-/// ```
-/// use im::vector::I64Vec2;
-///
-/// let g1 = I64Vec2::new(1, 2);
-/// let g2 = I64Vec2::new(3, 4);
-/// let distance = galaxy_manhattan_distance(&g1, &g2);
-/// assert_eq!(distance, 4);
-/// ```
-fn galaxy_manhattan_distance(g1: &I64Vec2, g2: &I64Vec2) -> i64 {
-    (g2.x - g1.x).abs() + (g2.y - g1.y).abs()
+fn print_stats(catalog: &GalaxyCatalog) {
+    println!("{} galaxies", catalog.len());
+    if let Some(pair) = catalog.nearest_pair() {
+        println!("nearest: {pair}");
+    }
+    if let Some(pair) = catalog.farthest_pair() {
+        println!("farthest: {pair}");
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use day11::catalog::GalaxyPair;
+    use day11::universe::galaxy_manhattan_distance;
+    use glam::I64Vec2;
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -145,4 +67,105 @@ mod tests {
         assert_eq!(part2(input, 10), 1030);
         assert_eq!(part2(input, 100), 8410);
     }
+
+    #[test]
+    fn d11p2_pairwise_sum_matches_pairs() {
+        let input = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+        let universe = Universe::new(&parse_input(input));
+        for expansion_factor in [1, 2, 10, 1_000_000] {
+            let galaxies = universe.expanded_galaxies(expansion_factor);
+            let pairwise: i64 = galaxies
+                .iter()
+                .tuple_combinations()
+                .map(|(g1, g2)| galaxy_manhattan_distance(g1, g2))
+                .sum();
+            assert_eq!(sum_of_pairwise_distances(&galaxies), pairwise as i128);
+        }
+
+        assert_eq!(sum_of_pairwise_distances(&[]), 0);
+        let far_apart = [I64Vec2::new(i64::MIN, 0), I64Vec2::new(i64::MAX, 0)];
+        assert_eq!(sum_of_pairwise_distances(&far_apart), u64::MAX as i128);
+    }
+
+    #[test]
+    fn d11p2_galaxy_catalog() {
+        let input = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+        let catalog = catalog(input, 2);
+        assert_eq!(catalog.len(), 9);
+        assert_eq!(catalog.galaxy(1), Some(I64Vec2::new(4, 0)));
+        assert_eq!(catalog.galaxy(0), None);
+        assert_eq!(catalog.galaxy(10), None);
+        assert_eq!(catalog.distance(5, 9), Some(9));
+        assert_eq!(catalog.distance(1, 7), Some(15));
+        assert_eq!(catalog.distance(3, 6), Some(17));
+        assert_eq!(catalog.distance(8, 9), Some(5));
+        assert_eq!(catalog.distance(1, 10), None);
+
+        let path: Vec<I64Vec2> = catalog
+            .shortest_path(5, 9)
+            .expect("should find path")
+            .collect();
+        assert_eq!(path.len(), 10);
+        assert_eq!(path.first().copied(), catalog.galaxy(5));
+        assert_eq!(path.last().copied(), catalog.galaxy(9));
+        assert!(path
+            .iter()
+            .tuple_windows()
+            .all(|(a, b)| galaxy_manhattan_distance(a, b) == 1));
+        assert_eq!(catalog.shortest_path(3, 3).map(Iterator::count), Some(1));
+
+        let csv = catalog.distance_matrix_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[0], ",1,2,3,4,5,6,7,8,9");
+        assert_eq!(rows[5], "5,9,13,5,8,0,12,12,6,9");
+
+        let pairs: Vec<GalaxyPair> = (1..=9)
+            .tuple_combinations()
+            .map(|(first, second)| GalaxyPair {
+                first,
+                second,
+                distance: catalog
+                    .distance(first, second)
+                    .expect("should find galaxies"),
+            })
+            .collect();
+        let nearest = pairs
+            .iter()
+            .min_by_key(|pair| (pair.distance, pair.first, pair.second));
+        assert_eq!(catalog.nearest_pair().as_ref(), nearest);
+        let farthest = catalog.farthest_pair().expect("should find farthest pair");
+        assert_eq!(
+            Some(farthest.distance),
+            pairs.iter().map(|pair| pair.distance).max()
+        );
+        assert_eq!(
+            catalog.distance(farthest.first, farthest.second),
+            Some(farthest.distance)
+        );
+
+        let single = GalaxyCatalog::new(vec![I64Vec2::ZERO]);
+        assert_eq!(single.nearest_pair(), None);
+        assert_eq!(single.farthest_pair(), None);
+    }
 }
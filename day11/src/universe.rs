@@ -0,0 +1,187 @@
+use glam::I64Vec2;
+
+use crate::parser::DataType;
+
+/// Galaxies of an observed image, together with the rows and columns that contain no galaxy.
+///
+/// Only the observed coordinates are stored. Expanded coordinates are computed from the empty rows
+/// and columns for any expansion factor, so the expanded grid is never materialized.
+#[derive(Debug)]
+pub struct Universe {
+    galaxies: Vec<I64Vec2>,
+    empty_rows: Vec<i64>,
+    empty_columns: Vec<i64>,
+}
+
+impl Universe {
+    /// Creates a universe from a parsed galaxy map.
+    ///
+    /// # Arguments
+    ///
+    /// * `galaxy_map` - A 2-dimensional slice representing the observed galaxy map.
+    ///
+    /// # Returns
+    ///
+    /// A universe holding the observed galaxies and the sorted indexes of empty rows and columns.
+    pub fn new(galaxy_map: &[Vec<DataType>]) -> Self {
+        let galaxies = get_galaxies_coordinates(galaxy_map);
+        let height = galaxy_map.len();
+        // Rows may have different lengths, so take the longest one to fit every galaxy
+        let width = galaxy_map.iter().map(Vec::len).max().unwrap_or(0);
+
+        // Mark the rows and columns that hold a galaxy in one pass, O(width + height + galaxies)
+        let mut occupied_rows = vec![false; height];
+        let mut occupied_columns = vec![false; width];
+        for galaxy in &galaxies {
+            occupied_rows[galaxy.y as usize] = true;
+            occupied_columns[galaxy.x as usize] = true;
+        }
+
+        let empty_rows = (0..height as i64)
+            .filter(|&y| !occupied_rows[y as usize])
+            .collect();
+        let empty_columns = (0..width as i64)
+            .filter(|&x| !occupied_columns[x as usize])
+            .collect();
+
+        Universe {
+            galaxies,
+            empty_rows,
+            empty_columns,
+        }
+    }
+
+    /// Returns the galaxy coordinates as observed, before any expansion.
+    pub fn galaxies(&self) -> &[I64Vec2] {
+        &self.galaxies
+    }
+
+    /// Calculates the galaxy coordinates after every empty row and column has grown.
+    ///
+    /// # Arguments
+    ///
+    /// * `expansion_factor` - How many rows or columns every empty one becomes. 1 leaves the
+    ///   universe unchanged and 2 doubles every empty row and column.
+    ///
+    /// # Returns
+    ///
+    /// The expanded coordinates, in the same order as [`Universe::galaxies`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use day11::parser::parse_input;
+    /// use day11::universe::Universe;
+    /// use glam::I64Vec2;
+    ///
+    /// let universe = Universe::new(&parse_input("#..\n...\n..#"));
+    /// assert_eq!(
+    ///     universe.expanded_galaxies(10),
+    ///     vec![I64Vec2::new(0, 0), I64Vec2::new(11, 11)]
+    /// );
+    /// ```
+    pub fn expanded_galaxies(&self, expansion_factor: i64) -> Vec<I64Vec2> {
+        let growth = expansion_factor - 1;
+
+        self.galaxies
+            .iter()
+            .map(|galaxy| {
+                let rows_before = self.empty_rows.partition_point(|&y| y < galaxy.y) as i64;
+                let columns_before = self.empty_columns.partition_point(|&x| x < galaxy.x) as i64;
+                *galaxy + I64Vec2::new(columns_before, rows_before) * growth
+            })
+            .collect()
+    }
+}
+
+/// Retrieves the coordinates of all galaxies in the provided galaxy map.
+///
+/// # Arguments
+///
+/// * `galaxy_map` - A 2-dimensional slice representing the galaxy map, where each position
+///   contains a DataType enum value.
+///
+/// # Returns
+///
+/// A vector of I64Vec2 objects, each representing the coordinates of a galaxy, in reading order.
+pub fn get_galaxies_coordinates(galaxy_map: &[Vec<DataType>]) -> Vec<I64Vec2> {
+    galaxy_map
+        .iter()
+        .enumerate()
+        .flat_map(|(row_idx, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, data_type)| **data_type == DataType::Galaxy)
+                .map(move |(col_idx, _)| I64Vec2::new(col_idx as i64, row_idx as i64))
+        })
+        .collect()
+}
+
+/// Calculates the Manhattan distance between two galaxies.
+///
+/// # Arguments
+///
+/// * `g1` - The first galaxy.
+/// * `g2` - The second galaxy.
+///
+/// # Returns
+///
+/// The Manhattan distance between the two galaxies.
+///
+/// # Example
+///
+/// ```
+/// use day11::universe::galaxy_manhattan_distance;
+/// use glam::I64Vec2;
+///
+/// let g1 = I64Vec2::new(1, 2);
+/// let g2 = I64Vec2::new(3, 4);
+/// let distance = galaxy_manhattan_distance(&g1, &g2);
+/// assert_eq!(distance, 4);
+/// ```
+pub fn galaxy_manhattan_distance(g1: &I64Vec2, g2: &I64Vec2) -> i64 {
+    (g2.x - g1.x).abs() + (g2.y - g1.y).abs()
+}
+
+/// Sums the Manhattan distances between every pair of galaxies without visiting every pair.
+///
+/// The distance is separable, so the x and y axes are summed independently. Once an axis is
+/// sorted, the value at index `i` is larger than the `i` values before it, and contributes
+/// `value * i - (sum of those values)` to the total. That is O(n log n) instead of O(n²).
+///
+/// # Arguments
+///
+/// * `galaxies` - The galaxy coordinates, in any order.
+///
+/// # Returns
+///
+/// The sum of the distances between all unordered pairs of galaxies.
+///
+/// # Example
+///
+/// ```
+/// use day11::universe::sum_of_pairwise_distances;
+/// use glam::I64Vec2;
+///
+/// let galaxies = [I64Vec2::new(0, 0), I64Vec2::new(2, 1), I64Vec2::new(1, 3)];
+/// assert_eq!(sum_of_pairwise_distances(&galaxies), 3 + 4 + 3);
+/// ```
+pub fn sum_of_pairwise_distances(galaxies: &[I64Vec2]) -> i128 {
+    sum_of_axis_distances(galaxies.iter().map(|galaxy| galaxy.x).collect())
+        + sum_of_axis_distances(galaxies.iter().map(|galaxy| galaxy.y).collect())
+}
+
+fn sum_of_axis_distances(mut values: Vec<i64>) -> i128 {
+    values.sort_unstable();
+
+    let mut prefix_sum: i128 = 0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(idx, &value)| {
+            let contribution = value as i128 * idx as i128 - prefix_sum;
+            prefix_sum += value as i128;
+            contribution
+        })
+        .sum()
+}
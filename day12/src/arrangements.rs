@@ -0,0 +1,372 @@
+use std::borrow::Cow;
+
+use rand::Rng;
+
+use crate::parser::{PuzzleLine, TileType};
+
+/// Dynamic programming table of the ways to finish a row from every position.
+///
+/// `ways(group, offset)` is the number of valid arrangements of `arrangements[group..]` over
+/// `tiles[offset..]`. The table is filled from the end of the row, so every entry only depends on
+/// entries that are already known, and it is owned by a single line, so it can't be shared by
+/// mistake between lines with different tiles or groups.
+#[derive(Debug, Clone)]
+pub struct ArrangementTable<'a> {
+    line: &'a PuzzleLine,
+    width: usize,
+    ways: Vec<u128>,
+    operational_before: Vec<usize>,
+}
+
+impl<'a> ArrangementTable<'a> {
+    /// Fills the table for `line` in O(tiles × groups).
+    ///
+    /// # Returns
+    ///
+    /// The table, or `None` if one of its counts does not fit into `u128`, which takes a line of
+    /// well over a hundred tiles.
+    pub fn new(line: &'a PuzzleLine) -> Option<Self> {
+        Self::fill(line, u128::checked_add)
+    }
+
+    /// Fills the table with counts that stop at `u128::MAX` instead of overflowing. Only good for
+    /// telling which states can still finish the row, which is all iteration needs.
+    fn saturating(line: &'a PuzzleLine) -> Self {
+        Self::fill(line, |a, b| Some(a.saturating_add(b))).expect("saturating add should not fail")
+    }
+
+    fn fill(line: &'a PuzzleLine, add: impl Fn(u128, u128) -> Option<u128>) -> Option<Self> {
+        let tiles = &line.tiles;
+        let groups = &line.arrangements;
+        let width = tiles.len() + 1;
+
+        // Operational tiles before each offset, so "no operational tile in a range" is O(1)
+        let mut operational_before = vec![0; width];
+        for (offset, tile) in tiles.iter().enumerate() {
+            operational_before[offset + 1] =
+                operational_before[offset] + usize::from(*tile == TileType::Operational);
+        }
+
+        let mut table = ArrangementTable {
+            line,
+            width,
+            ways: vec![0; width * (groups.len() + 1)],
+            operational_before,
+        };
+
+        // With no groups left the rest of the row must not contain a damaged tile
+        let last_row = groups.len() * width;
+        table.ways[last_row + tiles.len()] = 1;
+        for offset in (0..tiles.len()).rev() {
+            if tiles[offset] != TileType::Damaged {
+                table.ways[last_row + offset] = table.ways[last_row + offset + 1];
+            }
+        }
+
+        for group in (0..groups.len()).rev() {
+            for offset in (0..tiles.len()).rev() {
+                table.ways[group * width + offset] = add(
+                    table.skip_ways(group, offset),
+                    table.place_ways(group, offset),
+                )?;
+            }
+        }
+
+        Some(table)
+    }
+
+    /// Returns the number of valid arrangements of the whole line.
+    pub fn count(&self) -> u128 {
+        self.ways(0, 0)
+    }
+
+    /// Returns the number of valid arrangements of `arrangements[group..]` over `tiles[offset..]`.
+    pub fn ways(&self, group: usize, offset: usize) -> u128 {
+        self.ways[group * self.width + offset]
+    }
+
+    pub fn line(&self) -> &PuzzleLine {
+        self.line
+    }
+
+    /// Returns the arrangement with the given index, in the order [`ArrangementTable::iter`]
+    /// yields them, or `None` if there are not that many. Walks the row once, so it costs
+    /// O(tiles) no matter how many arrangements there are.
+    pub fn nth_arrangement(&self, mut index: u128) -> Option<PuzzleLine> {
+        if index >= self.count() {
+            return None;
+        }
+
+        let mut step = Step::start(self.line);
+        while step.group < self.line.arrangements.len() {
+            let place_ways = self.place_ways(step.group, step.offset);
+            if index < place_ways {
+                step = step.place(self.line);
+            } else {
+                index -= place_ways;
+                step = step.skip();
+            }
+        }
+
+        Some(step.finish(self.line))
+    }
+
+    /// Picks one of the arrangements uniformly at random, without enumerating them.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<PuzzleLine> {
+        match self.count() {
+            0 => None,
+            count => self.nth_arrangement(rng.gen_range(0..count)),
+        }
+    }
+
+    /// Lazily yields every valid arrangement, with each unknown tile replaced by an operational
+    /// or damaged one. Only branches that lead to at least one arrangement are explored, so
+    /// every step of the iterator produces an arrangement in O(tiles) amortized.
+    pub fn iter(&self) -> Arrangements<'_, 'a> {
+        Arrangements::new(Cow::Borrowed(self))
+    }
+
+    /// Works out what every tile is in each valid arrangement, the line-solving step of nonogram
+    /// solvers.
+    ///
+    /// The table already counts the ways to finish the row from every state. A forward pass
+    /// counts the ways to reach every state from the start of the row. Every placement of a group
+    /// then appears in `reach × finish` arrangements, which are added to the tiles it covers.
+    ///
+    /// # Returns
+    ///
+    /// The deduction, or `None` if the line has no valid arrangement.
+    pub fn deduce(&self) -> Option<Deduction> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+
+        let tiles = &self.line.tiles;
+        let groups = &self.line.arrangements;
+
+        // Ways to reach each state, only kept for states that can still finish the row so that
+        // every entry stays below the total count
+        let mut reach = vec![0u128; self.ways.len()];
+        reach[0] = 1;
+        // Damaged counts as a difference array, changed at the start and end of every placement.
+        // Entries may wrap around, but the running sums are always true counts
+        let mut damaged_delta = vec![0u128; self.width + 1];
+
+        for offset in 0..tiles.len() {
+            for group in 0..=groups.len() {
+                let ways_to_reach = reach[group * self.width + offset];
+                if ways_to_reach == 0 || self.ways(group, offset) == 0 {
+                    continue;
+                }
+
+                if self.skip_ways(group, offset) > 0 {
+                    reach[group * self.width + offset + 1] += ways_to_reach;
+                }
+
+                let place_ways = self.place_ways(group, offset);
+                if place_ways > 0 {
+                    let end = offset + groups[group];
+                    let next = (end + 1).min(tiles.len());
+                    reach[(group + 1) * self.width + next] += ways_to_reach;
+
+                    let arrangements = ways_to_reach * place_ways;
+                    damaged_delta[offset] = damaged_delta[offset].wrapping_add(arrangements);
+                    damaged_delta[end] = damaged_delta[end].wrapping_sub(arrangements);
+                }
+            }
+        }
+
+        let damaged_counts: Vec<u128> = damaged_delta[..tiles.len()]
+            .iter()
+            .scan(0u128, |damaged, delta| {
+                *damaged = damaged.wrapping_add(*delta);
+                Some(*damaged)
+            })
+            .collect();
+
+        let refined_tiles = damaged_counts
+            .iter()
+            .map(|&damaged| match damaged {
+                0 => TileType::Operational,
+                damaged if damaged == count => TileType::Damaged,
+                _ => TileType::Unknown,
+            })
+            .collect();
+
+        Some(Deduction {
+            line: PuzzleLine {
+                tiles: refined_tiles,
+                arrangements: groups.clone(),
+            },
+            damaged_probability: damaged_counts
+                .iter()
+                .map(|&damaged| damaged as f64 / count as f64)
+                .collect(),
+            damaged_counts,
+            count,
+        })
+    }
+
+    /// Ways to finish the row if the tile at `offset` is left operational.
+    fn skip_ways(&self, group: usize, offset: usize) -> u128 {
+        match self.line.tiles.get(offset) {
+            Some(TileType::Operational | TileType::Unknown) => self.ways(group, offset + 1),
+            _ => 0,
+        }
+    }
+
+    /// Ways to finish the row if `arrangements[group]` starts at `offset`.
+    fn place_ways(&self, group: usize, offset: usize) -> u128 {
+        let tiles = &self.line.tiles;
+        let Some(&len) = self.line.arrangements.get(group) else {
+            return 0;
+        };
+
+        // The group must fit, must not cover an operational tile and must be followed by an
+        // operational tile unless the row ends
+        let end = offset + len;
+        if end <= tiles.len()
+            && self.operational_before[end] == self.operational_before[offset]
+            && tiles.get(end) != Some(&TileType::Damaged)
+        {
+            self.ways(group + 1, (end + 1).min(tiles.len()))
+        } else {
+            0
+        }
+    }
+}
+
+/// What line solving found out about a row, created by [`ArrangementTable::deduce`].
+#[derive(Debug)]
+pub struct Deduction {
+    /// The row with every tile that is the same in all arrangements filled in, and `?` elsewhere.
+    pub line: PuzzleLine,
+    /// For every tile, the number of arrangements in which it is damaged.
+    pub damaged_counts: Vec<u128>,
+    /// For every tile, the share of arrangements in which it is damaged.
+    pub damaged_probability: Vec<f64>,
+    /// The number of valid arrangements.
+    pub count: u128,
+}
+
+/// Iterator over the concrete arrangements of a line, created by [`ArrangementTable::iter`].
+#[derive(Debug)]
+pub struct Arrangements<'t, 'a> {
+    table: Cow<'t, ArrangementTable<'a>>,
+    stack: Vec<Step>,
+}
+
+impl<'t, 'a> Arrangements<'t, 'a> {
+    fn new(table: Cow<'t, ArrangementTable<'a>>) -> Self {
+        let stack = if table.count() > 0 {
+            vec![Step::start(table.line)]
+        } else {
+            vec![]
+        };
+
+        Arrangements { table, stack }
+    }
+}
+
+impl Iterator for Arrangements<'_, '_> {
+    type Item = PuzzleLine;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.table.line;
+
+        while let Some(step) = self.stack.pop() {
+            if step.group == line.arrangements.len() {
+                return Some(step.finish(line));
+            }
+
+            // Pushed in reverse so placing the group comes out first, matching nth_arrangement
+            if self.table.skip_ways(step.group, step.offset) > 0 {
+                self.stack.push(step.clone().skip());
+            }
+            if self.table.place_ways(step.group, step.offset) > 0 {
+                self.stack.push(step.place(line));
+            }
+        }
+
+        None
+    }
+}
+
+/// A partially decided row: the tiles before `offset` are fixed and `arrangements[group..]` are
+/// still to be placed.
+#[derive(Debug, Clone)]
+struct Step {
+    group: usize,
+    offset: usize,
+    tiles: Vec<TileType>,
+}
+
+impl Step {
+    fn start(line: &PuzzleLine) -> Self {
+        Step {
+            group: 0,
+            offset: 0,
+            tiles: Vec::with_capacity(line.tiles.len()),
+        }
+    }
+
+    fn skip(mut self) -> Self {
+        self.tiles.push(TileType::Operational);
+        self.offset += 1;
+        self
+    }
+
+    fn place(mut self, line: &PuzzleLine) -> Self {
+        let len = line.arrangements[self.group];
+        self.tiles
+            .extend(std::iter::repeat_n(TileType::Damaged, len));
+        self.offset += len;
+        if self.offset < line.tiles.len() {
+            self.tiles.push(TileType::Operational);
+            self.offset += 1;
+        }
+        self.group += 1;
+        self
+    }
+
+    /// Marks the rest of the row operational, which the table guarantees is allowed.
+    fn finish(mut self, line: &PuzzleLine) -> PuzzleLine {
+        self.tiles.resize(line.tiles.len(), TileType::Operational);
+        PuzzleLine {
+            tiles: self.tiles,
+            arrangements: line.arrangements.clone(),
+        }
+    }
+}
+
+impl PuzzleLine {
+    /// Counts the valid arrangements of damaged tiles in the line, or returns `None` if the count
+    /// does not fit into `u128`.
+    pub fn arrangements_count(&self) -> Option<u128> {
+        ArrangementTable::new(self).map(|table| table.count())
+    }
+
+    /// Lazily yields every valid arrangement, see [`ArrangementTable::iter`]. Unlike the table this
+    /// also works for lines with more arrangements than fit into `u128`, since it only needs to
+    /// know which choices lead to at least one arrangement.
+    pub fn arrangements(&self) -> Arrangements<'_, '_> {
+        Arrangements::new(Cow::Owned(ArrangementTable::saturating(self)))
+    }
+
+    /// Picks one of the arrangements uniformly at random, see [`ArrangementTable::sample`].
+    ///
+    /// # Returns
+    ///
+    /// The arrangement, or `None` if the line has no valid arrangement or more than fit into
+    /// `u128`, as a uniform pick needs the exact count.
+    pub fn sample_arrangement<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<PuzzleLine> {
+        ArrangementTable::new(self)?.sample(rng)
+    }
+
+    /// Finds the tiles that are forced in every valid arrangement, see [`ArrangementTable::deduce`].
+    /// Also returns `None` if the arrangements can't be counted in `u128`.
+    pub fn deduce(&self) -> Option<Deduction> {
+        ArrangementTable::new(self)?.deduce()
+    }
+}
@@ -0,0 +1,55 @@
+use common::number_theory::gcd;
+
+use crate::parser::{PuzzleLine, TileType};
+
+/// Arrangement counts of a line unfolded into 1, 2, ... copies.
+#[derive(Debug)]
+pub struct UnfoldGrowth {
+    /// `counts[i]` is the number of arrangements with `i + 1` copies.
+    pub counts: Vec<u128>,
+}
+
+impl UnfoldGrowth {
+    /// Counts the arrangements of `line` unfolded into every number of copies up to `max_copies`,
+    /// or returns `None` if one of the counts does not fit into `u128`.
+    pub fn new(line: &PuzzleLine, max_copies: usize, separator: TileType) -> Option<Self> {
+        let counts = (1..=max_copies)
+            .map(|copies| line.unfold(copies, separator).arrangements_count())
+            .collect::<Option<Vec<u128>>>()?;
+
+        Some(UnfoldGrowth { counts })
+    }
+
+    /// Returns the ratio between each count and the previous one as a reduced fraction
+    /// `(numerator, denominator)`, or `None` where the previous count is 0.
+    pub fn ratios(&self) -> Vec<Option<(u128, u128)>> {
+        self.counts
+            .windows(2)
+            .map(|pair| {
+                (pair[0] != 0).then(|| {
+                    let divisor = gcd(pair[1], pair[0]);
+                    (pair[1] / divisor, pair[0] / divisor)
+                })
+            })
+            .collect()
+    }
+
+    /// Finds where the counts become a geometric sequence, that is where every following count is
+    /// the previous one times the same ratio. At least two equal ratios are needed to call it.
+    ///
+    /// # Returns
+    ///
+    /// The number of copies the geometric run starts at and its ratio, or `None` if the last
+    /// two ratios already differ.
+    pub fn geometric_from(&self) -> Option<(usize, (u128, u128))> {
+        let ratios = self.ratios();
+        let last = (*ratios.last()?)?;
+        let run = ratios
+            .iter()
+            .rev()
+            .take_while(|ratio| **ratio == Some(last))
+            .count();
+
+        (run >= 2).then_some((ratios.len() - run + 1, last))
+    }
+}
@@ -0,0 +1,4 @@
+pub mod arrangements;
+pub mod growth;
+pub mod nonogram;
+pub mod parser;
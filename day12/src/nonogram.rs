@@ -0,0 +1,272 @@
+use std::error::Error;
+use std::fmt;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::space0;
+use nom::combinator::all_consuming;
+use nom::multi::separated_list1;
+use nom::sequence::delimited;
+
+use crate::arrangements::ArrangementTable;
+use crate::parser::{parse_usize, PuzzleLine, TileType};
+
+/// A nonogram puzzle: the clues for every row from top to bottom and every column from left to
+/// right. Each clue lists the lengths of the filled runs, like the groups of a day 12 record.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Nonogram {
+    pub row_clues: Vec<Vec<usize>>,
+    pub column_clues: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum NonogramError {
+    /// A line could not be parsed.
+    Syntax(String),
+    /// A required key or section is missing from the file.
+    Missing(&'static str),
+    /// The number of clues in a section does not match the declared width or height.
+    ClueCountMismatch {
+        section: &'static str,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for NonogramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NonogramError::Syntax(line) => write!(f, "unable to parse line: {line}"),
+            NonogramError::Missing(key) => write!(f, "missing {key}"),
+            NonogramError::ClueCountMismatch {
+                section,
+                expected,
+                found,
+            } => write!(f, "expected {expected} {section} clues, found {found}"),
+        }
+    }
+}
+
+impl Error for NonogramError {}
+
+/// A filled in grid, with damaged tiles as filled cells and operational tiles as blank ones.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Picture {
+    pub cells: Vec<Vec<TileType>>,
+}
+
+impl fmt::Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.cells {
+            for cell in row {
+                let symbol = match cell {
+                    TileType::Damaged => '#',
+                    TileType::Operational => '.',
+                    TileType::Unknown => '?',
+                };
+                write!(f, "{symbol}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of solving a nonogram. The search stops at the second solution it finds.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Solution {
+    NoSolution,
+    Unique(Picture),
+    Multiple(Picture, Picture),
+}
+
+impl Nonogram {
+    pub fn width(&self) -> usize {
+        self.column_clues.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.row_clues.len()
+    }
+
+    /// Solves the puzzle.
+    ///
+    /// Every row and column is line solved with [`ArrangementTable::deduce`] until nothing changes.
+    /// If cells are still unknown, the first one is guessed filled and then blank, and each guess
+    /// is solved the same way.
+    pub fn solve(&self) -> Solution {
+        let grid = vec![vec![TileType::Unknown; self.width()]; self.height()];
+        let mut solutions = Vec::new();
+        self.search(grid, &mut solutions);
+
+        let mut solutions = solutions.into_iter();
+        match (solutions.next(), solutions.next()) {
+            (None, _) => Solution::NoSolution,
+            (Some(picture), None) => Solution::Unique(picture),
+            (Some(first), Some(second)) => Solution::Multiple(first, second),
+        }
+    }
+
+    fn search(&self, mut grid: Vec<Vec<TileType>>, solutions: &mut Vec<Picture>) {
+        if solutions.len() >= 2 || !self.propagate(&mut grid) {
+            return;
+        }
+
+        let unknown = grid.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|cell| *cell == TileType::Unknown)
+                .map(|x| (x, y))
+        });
+
+        match unknown {
+            None => solutions.push(Picture { cells: grid }),
+            Some((x, y)) => {
+                for guess in [TileType::Damaged, TileType::Operational] {
+                    let mut guessed = grid.clone();
+                    guessed[y][x] = guess;
+                    self.search(guessed, solutions);
+                }
+            }
+        }
+    }
+
+    /// Line solves rows and columns until a fixpoint. Returns false if a line has no valid
+    /// arrangement left.
+    fn propagate(&self, grid: &mut [Vec<TileType>]) -> bool {
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for (y, clue) in self.row_clues.iter().enumerate() {
+                let line = PuzzleLine {
+                    tiles: grid[y].clone(),
+                    arrangements: clue.clone(),
+                };
+                let Some(table) = ArrangementTable::new(&line) else {
+                    // Too many arrangements to count, so the line is left for later
+                    continue;
+                };
+                let Some(deduction) = table.deduce() else {
+                    return false;
+                };
+                for (x, tile) in deduction.line.tiles.into_iter().enumerate() {
+                    if grid[y][x] != tile {
+                        grid[y][x] = tile;
+                        changed = true;
+                    }
+                }
+            }
+
+            for (x, clue) in self.column_clues.iter().enumerate() {
+                let line = PuzzleLine {
+                    tiles: grid.iter().map(|row| row[x]).collect(),
+                    arrangements: clue.clone(),
+                };
+                let Some(table) = ArrangementTable::new(&line) else {
+                    // Too many arrangements to count, so the line is left for later
+                    continue;
+                };
+                let Some(deduction) = table.deduce() else {
+                    return false;
+                };
+                for (y, tile) in deduction.line.tiles.into_iter().enumerate() {
+                    if grid[y][x] != tile {
+                        grid[y][x] = tile;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Parses a nonogram in the common `.non` format.
+///
+/// The file declares `width` and `height`, followed by a `rows` and a `columns` section with one
+/// comma-separated clue per line, where `0` marks an empty line. Other keys such as `title` or
+/// `author` are ignored.
+///
+/// # Example
+///
+/// ```
+/// use day12::nonogram::parse_nonogram;
+///
+/// let input = "title \"Corner\"\nwidth 2\nheight 2\n\nrows\n2\n1\n\ncolumns\n2\n1\n";
+/// let nonogram = parse_nonogram(input).expect("should parse nonogram");
+/// assert_eq!(nonogram.row_clues, vec![vec![2], vec![1]]);
+/// assert_eq!(nonogram.column_clues, vec![vec![2], vec![1]]);
+/// ```
+pub fn parse_nonogram(input: &str) -> Result<Nonogram, NonogramError> {
+    let mut width = None;
+    let mut height = None;
+    let mut row_clues = None;
+    let mut column_clues = None;
+
+    let mut lines = input.lines().map(str::trim).peekable();
+    while let Some(line) = lines.next() {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "width" => width = Some(parse_dimension(line, value)?),
+            "height" => height = Some(parse_dimension(line, value)?),
+            "rows" | "columns" => {
+                let mut clues = Vec::new();
+                while let Some(clue) = lines.next_if(|line| starts_with_digit(line)) {
+                    clues.push(parse_clue(clue)?);
+                }
+                if key == "rows" {
+                    row_clues = Some(clues);
+                } else {
+                    column_clues = Some(clues);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let width = width.ok_or(NonogramError::Missing("width"))?;
+    let height = height.ok_or(NonogramError::Missing("height"))?;
+    let row_clues = row_clues.ok_or(NonogramError::Missing("rows"))?;
+    let column_clues = column_clues.ok_or(NonogramError::Missing("columns"))?;
+
+    for (section, expected, found) in [
+        ("row", height, row_clues.len()),
+        ("column", width, column_clues.len()),
+    ] {
+        if expected != found {
+            return Err(NonogramError::ClueCountMismatch {
+                section,
+                expected,
+                found,
+            });
+        }
+    }
+
+    Ok(Nonogram {
+        row_clues,
+        column_clues,
+    })
+}
+
+fn starts_with_digit(line: &str) -> bool {
+    line.starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn parse_dimension(line: &str, value: &str) -> Result<usize, NonogramError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| NonogramError::Syntax(line.to_string()))
+}
+
+fn parse_clue(line: &str) -> Result<Vec<usize>, NonogramError> {
+    let (_, clue) = all_consuming(separated_list1(
+        delimited(space0, tag(","), space0),
+        parse_usize,
+    ))(line)
+    .map_err(|_| NonogramError::Syntax(line.to_string()))?;
+
+    // A single 0 is an empty line
+    Ok(clue.into_iter().filter(|&len| len > 0).collect())
+}
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
@@ -29,75 +28,6 @@ pub struct PuzzleLine {
     pub arrangements: Vec<usize>,
 }
 
-/// Calculates the count of valid arrangements of tiles with the given configurations and cache.
-///
-/// # Arguments
-///
-/// * `tiles` - A slice of `TileType` representing the tiles.
-/// * `arrangements` - A slice of `usize` representing the configurations of the tiles.
-/// * `cache` - A mutable `HashMap` storing the previously calculated count of arrangements.
-///
-/// # Returns
-///
-/// The count of valid arrangements based on the given tiles and configurations.
-pub fn arrangements_count_with_cache(
-    tiles: &[TileType],
-    arrangements: &[usize],
-    cache: &mut HashMap<(usize, usize), usize>,
-) -> usize {
-    if let Some(count) = cache.get(&(tiles.len(), arrangements.len())) {
-        return *count;
-    }
-
-    let mut count = 0;
-
-    if arrangements.is_empty() {
-        count = if tiles.contains(&TileType::Damaged) {
-            0
-        } else {
-            1
-        };
-
-        cache.insert((tiles.len(), arrangements.len()), count);
-        return count;
-    }
-
-    for offset in 0..tiles.len() {
-        if tiles[0..offset].contains(&TileType::Damaged) || offset + arrangements[0] > tiles.len() {
-            break;
-        }
-
-        if tiles[offset..offset + arrangements[0]].contains(&TileType::Operational) {
-            continue;
-        }
-
-        if arrangements.len() == 1 {
-            if offset + arrangements[0] == tiles.len() {
-                count += 1;
-                break;
-            } else {
-                count +=
-                    arrangements_count_with_cache(&tiles[offset + arrangements[0]..], &[], cache);
-                continue;
-            };
-        } else if offset + arrangements[0] + 1 > tiles.len() {
-            break;
-        } else if tiles[offset + arrangements[0]] == TileType::Damaged {
-            continue;
-        }
-
-        count += arrangements_count_with_cache(
-            &tiles[offset + arrangements[0] + 1..],
-            &arrangements[1..],
-            cache,
-        );
-    }
-
-    cache.insert((tiles.len(), arrangements.len()), count);
-
-    count
-}
-
 impl PuzzleLine {
     /// Unfolds the records of the object by adding more copies of its tiles and arrangements.
     ///
@@ -109,14 +39,27 @@ impl PuzzleLine {
     ///
     /// Returns a new object with unfolded tiles and arrangements.
     pub fn unfold_records(&self) -> Self {
-        // To unfold the records, on each row, replace the list of spring tiles with five copies of itself
-        // (separated by ?) and replace the list of contiguous groups of damaged springs with five copies of itself
-        // (separated by ,)
-        let mut unfolded_tiles = self.tiles.clone();
-        let mut unfolded_arrangements = self.arrangements.clone();
+        self.unfold(5, TileType::Unknown)
+    }
 
-        for _ in 0..4 {
-            unfolded_tiles.push(TileType::Unknown);
+    /// Unfolds the records into any number of copies of the tiles and arrangements.
+    ///
+    /// # Arguments
+    ///
+    /// * `copies` - How many copies of the tiles and arrangements the unfolded line holds.
+    /// * `separator` - The tile placed between two copies of the tiles.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new object with unfolded tiles and arrangements.
+    pub fn unfold(&self, copies: usize, separator: TileType) -> Self {
+        let mut unfolded_tiles = Vec::with_capacity((self.tiles.len() + 1) * copies);
+        let mut unfolded_arrangements = Vec::with_capacity(self.arrangements.len() * copies);
+
+        for copy in 0..copies {
+            if copy > 0 {
+                unfolded_tiles.push(separator);
+            }
             unfolded_tiles.extend(&self.tiles);
             unfolded_arrangements.extend(&self.arrangements);
         }
@@ -229,6 +172,6 @@ pub fn parse_line(input_line: &str) -> IResult<&str, PuzzleLine> {
 ///
 /// * `Err(nom::Err)` - Represents a parsing failure, where `nom::Err` is an `enum`
 ///   with various variants indicating different types of parsing errors.
-fn parse_usize(input: &str) -> IResult<&str, usize> {
+pub(crate) fn parse_usize(input: &str) -> IResult<&str, usize> {
     map_res(digit1, str::parse::<usize>)(input)
 }
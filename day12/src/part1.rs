@@ -1,34 +1,74 @@
-use std::collections::HashMap;
+use std::env;
 use std::time::Instant;
 
-use crate::parser::{arrangements_count_with_cache, parse_input};
-
-mod parser;
+use day12::arrangements::ArrangementTable;
+use day12::parser::parse_input;
+use itertools::Itertools;
 
 fn main() {
     let start_time = Instant::now();
     let input_file = include_str!("../input_p1.txt");
+    if env::args().any(|arg| arg == "--show") {
+        print_arrangements(input_file, 10);
+    }
+    if env::args().any(|arg| arg == "--deduce") {
+        print_deductions(input_file);
+    }
     let solution = part1(input_file);
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
 
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> u128 {
     let (_, parsed_input) = parse_input(input).expect("should parse input");
     parsed_input
         .iter()
         .map(|puzzle_line| {
-            arrangements_count_with_cache(
-                &puzzle_line.tiles,
-                &puzzle_line.arrangements,
-                &mut HashMap::new(),
-            )
+            puzzle_line
+                .arrangements_count()
+                .expect("arrangement count should fit in u128")
         })
         .sum()
 }
 
+fn print_arrangements(input: &str, limit: usize) {
+    let (_, parsed_input) = parse_input(input).expect("should parse input");
+    for puzzle_line in &parsed_input {
+        let table =
+            ArrangementTable::new(puzzle_line).expect("arrangement count should fit in u128");
+        println!("{puzzle_line} ({} arrangements)", table.count());
+        table
+            .iter()
+            .take(limit)
+            .for_each(|arrangement| println!("  {arrangement}"));
+    }
+}
+
+fn print_deductions(input: &str) {
+    let (_, parsed_input) = parse_input(input).expect("should parse input");
+    for puzzle_line in &parsed_input {
+        match puzzle_line.deduce() {
+            Some(deduction) => {
+                let probabilities = deduction
+                    .damaged_probability
+                    .iter()
+                    .map(|probability| format!("{probability:.2}"))
+                    .join(" ");
+                println!("{puzzle_line} -> {} [{probabilities}]", deduction.line);
+            }
+            None => println!("{puzzle_line} -> no valid arrangement"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use day12::parser::{parse_line, PuzzleLine, TileType};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
     use super::*;
 
     #[test]
@@ -42,4 +82,119 @@ mod tests {
 
         assert_eq!(part1(input), 21);
     }
+
+    #[test]
+    fn d12p1_arrangement_table() {
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+# 1
+#. 2
+??? 1,1
+?#?? 1";
+
+        let (_, parsed_input) = parse_input(input).expect("should parse input");
+        let counts: Vec<u128> = parsed_input
+            .iter()
+            .map(|puzzle_line| puzzle_line.arrangements_count())
+            .collect::<Option<Vec<u128>>>()
+            .expect("arrangement counts should fit in u128");
+        assert_eq!(counts, vec![1, 4, 1, 1, 4, 10, 1, 0, 1, 1]);
+
+        let table = ArrangementTable::new(&parsed_input[5]).expect("should fill table");
+        assert_eq!(table.ways(0, 0), 10);
+        assert_eq!(table.ways(3, 12), 1);
+        assert_eq!(table.ways(3, 3), 0);
+    }
+
+    #[test]
+    fn d12p1_arrangements_iter() {
+        let (_, puzzle_line) = parse_line("?###???????? 3,2,1").expect("should parse line");
+        let table = ArrangementTable::new(&puzzle_line).expect("should fill table");
+        let arrangements: Vec<PuzzleLine> = table.iter().collect();
+        assert_eq!(arrangements.len(), 10);
+        assert_eq!(arrangements[0].to_string(), ".###.##.#... 3,2,1");
+        assert_eq!(arrangements[9].to_string(), ".###....##.# 3,2,1");
+
+        let rendered: HashSet<String> = arrangements.iter().map(ToString::to_string).collect();
+        assert_eq!(rendered.len(), 10);
+        for (index, arrangement) in arrangements.iter().enumerate() {
+            assert!(!arrangement.tiles.contains(&TileType::Unknown));
+            assert!(puzzle_line
+                .tiles
+                .iter()
+                .zip(&arrangement.tiles)
+                .all(|(tile, chosen)| *tile == TileType::Unknown || tile == chosen));
+            assert_eq!(arrangement.arrangements_count(), Some(1));
+            assert_eq!(
+                table
+                    .nth_arrangement(index as u128)
+                    .map(|line| line.to_string()),
+                Some(arrangement.to_string())
+            );
+        }
+        assert!(table.nth_arrangement(10).is_none());
+        assert_eq!(table.iter().take(3).count(), 3);
+
+        let from_line: Vec<String> = puzzle_line
+            .arrangements()
+            .map(|arrangement| arrangement.to_string())
+            .collect();
+        let from_table: Vec<String> = arrangements.iter().map(ToString::to_string).collect();
+        assert_eq!(from_line, from_table);
+
+        let mut rng = StdRng::seed_from_u64(12);
+        for _ in 0..20 {
+            let sample = table.sample(&mut rng).expect("should sample arrangement");
+            assert!(rendered.contains(&sample.to_string()));
+            let sample = puzzle_line
+                .sample_arrangement(&mut rng)
+                .expect("should sample arrangement");
+            assert!(rendered.contains(&sample.to_string()));
+        }
+
+        let (_, impossible) = parse_line("#. 2").expect("should parse line");
+        let table = ArrangementTable::new(&impossible).expect("should fill table");
+        assert!(table.iter().next().is_none());
+        assert!(table.sample(&mut rng).is_none());
+        assert!(impossible.arrangements().next().is_none());
+        assert!(impossible.sample_arrangement(&mut rng).is_none());
+    }
+
+    #[test]
+    fn d12p1_deduce() {
+        let (_, puzzle_line) = parse_line("?###???????? 3,2,1").expect("should parse line");
+        let deduction = puzzle_line.deduce().expect("should deduce line");
+        assert_eq!(deduction.count, 10);
+        assert_eq!(deduction.line.to_string(), ".###.??????? 3,2,1");
+
+        // Every count must match the concrete arrangements
+        let mut damaged_counts = vec![0u128; puzzle_line.tiles.len()];
+        for arrangement in ArrangementTable::new(&puzzle_line)
+            .expect("should fill table")
+            .iter()
+        {
+            for (idx, tile) in arrangement.tiles.iter().enumerate() {
+                damaged_counts[idx] += u128::from(*tile == TileType::Damaged);
+            }
+        }
+        assert_eq!(deduction.damaged_counts, damaged_counts);
+        assert_eq!(deduction.damaged_probability[1], 1.0);
+        assert_eq!(deduction.damaged_probability[4], 0.0);
+        assert_eq!(deduction.damaged_probability[5], 0.4);
+
+        let (_, puzzle_line) = parse_line("??????? 2,1,2").expect("should parse line");
+        let deduction = puzzle_line.deduce().expect("should deduce line");
+        assert_eq!(deduction.line.to_string(), "##.#.## 2,1,2");
+
+        let (_, puzzle_line) = parse_line("???????? 5").expect("should parse line");
+        let deduction = puzzle_line.deduce().expect("should deduce line");
+        assert_eq!(deduction.line.to_string(), "???##??? 5");
+
+        let (_, puzzle_line) = parse_line("#. 2").expect("should parse line");
+        assert!(puzzle_line.deduce().is_none());
+    }
 }
@@ -1,21 +1,23 @@
-use std::collections::HashMap;
+use std::env;
 use std::time::Instant;
 
 use rayon::prelude::*;
 
-use crate::parser::{arrangements_count_with_cache, parse_input, PuzzleLine};
-
-mod parser;
+use day12::growth::UnfoldGrowth;
+use day12::parser::{parse_input, PuzzleLine, TileType};
 
 fn main() {
     let start_time = Instant::now();
-    let input_file = include_str!("../input_p1.txt");
+    let input_file = include_str!("../input_p2.txt");
+    if env::args().any(|arg| arg == "--growth") {
+        print_growth_report(input_file, 8);
+    }
     let solution = part2(input_file);
     let execution_time = start_time.elapsed().as_secs_f64();
     println!("[{execution_time:?} seconds] {solution}");
 }
 
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> u128 {
     let (_, parsed_input) = parse_input(input).expect("should parse input");
 
     let unfolded_tiles: Vec<PuzzleLine> = parsed_input
@@ -26,17 +28,52 @@ fn part2(input: &str) -> usize {
     unfolded_tiles
         .par_iter()
         .map(|puzzle_line| {
-            arrangements_count_with_cache(
-                &puzzle_line.tiles,
-                &puzzle_line.arrangements,
-                &mut HashMap::new(),
-            )
+            puzzle_line
+                .arrangements_count()
+                .expect("arrangement count should fit in u128")
         })
         .sum()
 }
 
+fn print_growth_report(input: &str, max_copies: usize) {
+    let (_, parsed_input) = parse_input(input).expect("should parse input");
+
+    let reports: Vec<(String, UnfoldGrowth)> = parsed_input
+        .par_iter()
+        .map(|puzzle_line| {
+            let growth = UnfoldGrowth::new(puzzle_line, max_copies, TileType::Unknown)
+                .expect("arrangement counts should fit in u128");
+            (puzzle_line.to_string(), growth)
+        })
+        .collect();
+
+    let mut geometric_lines = 0;
+    for (line, growth) in &reports {
+        match growth.geometric_from() {
+            Some((copies, (numerator, denominator))) => {
+                geometric_lines += 1;
+                println!(
+                    "{line}: {:?}, geometric from {copies} copies with ratio {numerator}/{denominator}",
+                    growth.counts
+                );
+            }
+            None => println!("{line}: {:?}, not geometric", growth.counts),
+        }
+    }
+    println!(
+        "{geometric_lines} of {} lines are geometric within {max_copies} copies",
+        reports.len()
+    );
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use day12::parser::parse_line;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
     use super::*;
 
     #[test]
@@ -50,4 +87,154 @@ mod tests {
 
         assert_eq!(part2(input), 525152);
     }
+
+    #[test]
+    fn d12p2_unfolded_lines() {
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+        let (_, parsed_input) = parse_input(input).expect("should parse input");
+        let counts: Vec<u128> = parsed_input
+            .iter()
+            .map(|puzzle_line| puzzle_line.unfold_records().arrangements_count())
+            .collect::<Option<Vec<u128>>>()
+            .expect("arrangement counts should fit in u128");
+        assert_eq!(counts, vec![1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn d12p2_arrangements_beyond_u128() {
+        let (_, puzzle_line) = parse_line(".??..??...?##. 1,1,3").expect("should parse line");
+        // 2^128 arrangements, one more than u128 can hold
+        let unfolded = puzzle_line.unfold(43, TileType::Unknown);
+        assert_eq!(unfolded.arrangements_count(), None);
+
+        let arrangements: Vec<PuzzleLine> = unfolded.arrangements().take(3).collect();
+        assert_eq!(arrangements.len(), 3);
+        for arrangement in &arrangements {
+            assert!(!arrangement.tiles.contains(&TileType::Unknown));
+            assert_eq!(arrangement.arrangements_count(), Some(1));
+        }
+        assert_ne!(arrangements[0].to_string(), arrangements[1].to_string());
+
+        let mut rng = StdRng::seed_from_u64(12);
+        assert!(unfolded.sample_arrangement(&mut rng).is_none());
+    }
+
+    #[test]
+    fn d12p2_unfold_growth() {
+        let (_, puzzle_line) = parse_line(".??..??...?##. 1,1,3").expect("should parse line");
+        assert_eq!(
+            puzzle_line.unfold(1, TileType::Unknown).to_string(),
+            puzzle_line.to_string()
+        );
+        assert_eq!(
+            puzzle_line.unfold(2, TileType::Operational).to_string(),
+            ".??..??...?##...??..??...?##. 1,1,3,1,1,3"
+        );
+        assert_eq!(
+            puzzle_line
+                .unfold(3, TileType::Operational)
+                .arrangements_count(),
+            Some(64)
+        );
+        assert_eq!(
+            puzzle_line
+                .unfold(25, TileType::Unknown)
+                .arrangements_count(),
+            Some(1 << 74)
+        );
+        // 4 × 8^42 = 2^128 arrangements, one more than u128 can hold
+        assert_eq!(
+            puzzle_line
+                .unfold(43, TileType::Unknown)
+                .arrangements_count(),
+            None
+        );
+        assert!(UnfoldGrowth::new(&puzzle_line, 43, TileType::Unknown).is_none());
+
+        let growth = UnfoldGrowth::new(&puzzle_line, 6, TileType::Unknown).expect("should count");
+        assert_eq!(growth.counts, vec![4, 32, 256, 2048, 16384, 131072]);
+        assert_eq!(growth.geometric_from(), Some((1, (8, 1))));
+
+        let (_, puzzle_line) = parse_line("?.?#?#??.?? 1,3").expect("should parse line");
+        let growth = UnfoldGrowth::new(&puzzle_line, 5, TileType::Unknown).expect("should count");
+        assert_eq!(growth.counts, vec![2, 11, 64, 373, 2174]);
+        assert_eq!(growth.ratios()[0], Some((11, 2)));
+        assert_eq!(growth.geometric_from(), None);
+
+        let (_, puzzle_line) = parse_line("#. 2").expect("should parse line");
+        let growth = UnfoldGrowth::new(&puzzle_line, 3, TileType::Unknown).expect("should count");
+        assert_eq!(growth.counts, vec![0, 0, 0]);
+        assert_eq!(growth.ratios(), vec![None, None]);
+        assert_eq!(growth.geometric_from(), None);
+    }
+
+    /// The memoized recursion the DP table replaced, kept to measure the speedup against.
+    fn memoized_count(
+        tiles: &[TileType],
+        groups: &[usize],
+        cache: &mut HashMap<(usize, usize), u128>,
+    ) -> u128 {
+        if let Some(count) = cache.get(&(tiles.len(), groups.len())) {
+            return *count;
+        }
+
+        let Some((&len, rest)) = groups.split_first() else {
+            return u128::from(!tiles.contains(&TileType::Damaged));
+        };
+
+        let mut count = 0;
+        for offset in 0..tiles.len() {
+            if tiles[..offset].contains(&TileType::Damaged) || offset + len > tiles.len() {
+                break;
+            }
+            if tiles[offset..offset + len].contains(&TileType::Operational) {
+                continue;
+            }
+            if offset + len == tiles.len() {
+                count += u128::from(rest.is_empty());
+                break;
+            }
+            if tiles[offset + len] != TileType::Damaged {
+                count += memoized_count(&tiles[offset + len + 1..], rest, cache);
+            }
+        }
+
+        cache.insert((tiles.len(), groups.len()), count);
+        count
+    }
+
+    #[test]
+    #[ignore = "timing, run with cargo test --release -p day12 --bin d12p2 -- --ignored --nocapture"]
+    fn d12p2_dp_table_timing() {
+        let (_, parsed_input) =
+            parse_input(include_str!("../input_p2.txt")).expect("should parse input");
+        let unfolded: Vec<PuzzleLine> = parsed_input
+            .iter()
+            .map(|puzzle_line| puzzle_line.unfold_records())
+            .collect();
+
+        let start_time = Instant::now();
+        let memoized: u128 = unfolded
+            .iter()
+            .map(|line| memoized_count(&line.tiles, &line.arrangements, &mut HashMap::new()))
+            .sum();
+        let memoized_time = start_time.elapsed();
+
+        let start_time = Instant::now();
+        let dp_table: u128 = unfolded
+            .iter()
+            .map(|line| line.arrangements_count().expect("count should fit in u128"))
+            .sum();
+        let dp_table_time = start_time.elapsed();
+
+        println!("memoized recursion: {memoized_time:?}, DP table: {dp_table_time:?}");
+        assert_eq!(memoized, dp_table);
+        assert!(dp_table_time < memoized_time);
+    }
 }
@@ -0,0 +1,162 @@
+use std::env;
+use std::fs;
+use std::time::Instant;
+
+use day12::nonogram::{parse_nonogram, Solution};
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .expect("should pass the path of a .non puzzle file");
+    let input = fs::read_to_string(&path).expect("should read puzzle file");
+
+    let start_time = Instant::now();
+    let solution = solve(&input);
+    let execution_time = start_time.elapsed().as_secs_f64();
+    println!("[{execution_time:?} seconds]\n{solution}");
+}
+
+fn solve(input: &str) -> String {
+    let nonogram = match parse_nonogram(input) {
+        Ok(nonogram) => nonogram,
+        Err(error) => return format!("invalid puzzle: {error}"),
+    };
+
+    match nonogram.solve() {
+        Solution::NoSolution => "no solution".to_string(),
+        Solution::Unique(picture) => picture.to_string(),
+        Solution::Multiple(first, second) => {
+            format!("multiple solutions, for example:\n{first}\nand\n{second}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use day12::nonogram::NonogramError;
+
+    use super::*;
+
+    #[test]
+    fn d12nonogram_line_solvable() {
+        let input = "title \"Letter A\"
+width 5
+height 5
+
+rows
+3
+1,1
+5
+1,1
+1,1
+
+columns
+4
+1,1
+1,1
+1,1
+4";
+
+        assert_eq!(
+            solve(input),
+            ".###.
+#...#
+#####
+#...#
+#...#
+"
+        );
+    }
+
+    #[test]
+    fn d12nonogram_needs_guessing() {
+        // Line solving alone leaves cells unknown here, but only one guess leads to a picture
+        let input = "width 5
+height 5
+rows
+2
+1,2
+1
+1,1
+1,2
+columns
+2
+1,1
+1
+2,2
+1,1";
+
+        assert_eq!(
+            solve(input),
+            "..##.
+.#.##
+#....
+#..#.
+.#.##
+"
+        );
+    }
+
+    #[test]
+    fn d12nonogram_multiple_solutions() {
+        let input = "width 2
+height 2
+rows
+1
+1
+columns
+1
+1";
+
+        let nonogram = parse_nonogram(input).expect("should parse nonogram");
+        let Solution::Multiple(first, second) = nonogram.solve() else {
+            panic!("should find two solutions");
+        };
+        assert_eq!(first.to_string(), "#.\n.#\n");
+        assert_eq!(second.to_string(), ".#\n#.\n");
+        assert!(solve(input).starts_with("multiple solutions"));
+    }
+
+    #[test]
+    fn d12nonogram_no_solution() {
+        let input = "width 2
+height 2
+rows
+2
+0
+columns
+1
+0";
+
+        assert_eq!(
+            parse_nonogram(input).map(|nonogram| nonogram.solve()),
+            Ok(Solution::NoSolution)
+        );
+        assert_eq!(solve(input), "no solution");
+    }
+
+    #[test]
+    fn d12nonogram_invalid_files() {
+        assert_eq!(
+            parse_nonogram("width 2\nrows\n1\n1\ncolumns\n1\n1"),
+            Err(NonogramError::Missing("height"))
+        );
+        assert_eq!(
+            parse_nonogram("width 2\nheight 3\nrows\n1\n1\ncolumns\n1\n1"),
+            Err(NonogramError::ClueCountMismatch {
+                section: "row",
+                expected: 3,
+                found: 2,
+            })
+        );
+        assert_eq!(
+            parse_nonogram("width 1\nheight 1\nrows\n1;1\ncolumns\n1"),
+            Err(NonogramError::Syntax("1;1".to_string()))
+        );
+        assert_eq!(
+            parse_nonogram("width 1\nheight 1\nrows\n1, 0\ncolumns\n1")
+                .map(|nonogram| nonogram.row_clues),
+            Ok(vec![vec![1]])
+        );
+    }
+}